@@ -1,6 +1,32 @@
 use chrono::{NaiveDate, NaiveDateTime};
+use crate::attendance::Attendance;
+use crate::changes::Change;
+use crate::conflicts::Conflict;
+use crate::config::{AttendanceConfig, FreeTimeConfig, TravelConfig};
+use crate::exams::ExamTerm;
+use crate::notes::{NoteEditor, Notes};
+use crate::places::Directory;
+use crate::reminders::Reminders;
+use crate::results::StudyResult;
 use crate::timetable::{CourseAction, parse_course_datetime};
-use std::collections::HashSet;
+use crate::travel::TravelWarning;
+use std::collections::{HashMap, HashSet};
+
+/// What the main area shows below the status bar.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum View {
+    Table,
+    Week,
+    Day,
+    Month,
+    Courses,
+    Exams,
+    Results,
+    Conflicts,
+    Free,
+    Places,
+}
+
 pub struct App<'a> {
     pub courses: Vec<&'a CourseAction>,
     
@@ -31,7 +57,86 @@ pub struct App<'a> {
 
     pub show_clock: bool,
     pub last_update: Option<NaiveDateTime>,
-    pub offline_mode: bool
+    pub offline_mode: bool,
+
+    pub read_only: bool,
+
+    pub reminders: Option<Reminders>,
+
+    pub export_overlay_active: bool,
+
+    pub export_overlay_index: usize,
+
+    pub export_message: Option<String>,
+
+    pub view: View,
+
+    /// Day the calendar views are anchored to.
+    pub view_date: NaiveDate,
+
+    /// Index into the courses of the shown week or day, or the rows of the courses view.
+    pub view_selected: Option<usize>,
+
+    /// Course group whose sessions the courses view lists.
+    pub courses_group: Option<usize>,
+
+    pub show_weekends: bool,
+
+    /// Opened from a view other than the table, shown instead of the selected row.
+    pub details_course: Option<&'a CourseAction>,
+
+    /// Days with exams or own events, flagged in the month view.
+    pub marked_days: HashSet<NaiveDate>,
+
+    pub attendance: HashMap<String, Attendance>,
+
+    pub attendance_config: AttendanceConfig,
+
+    /// Daily bounds and minimum length of the free time view.
+    pub free_time: FreeTimeConfig,
+
+    pub notes: Notes,
+
+    pub note_editor: Option<NoteEditor>,
+
+    /// Registered and open exam terms from the last sync.
+    pub exam_terms: Vec<ExamTerm>,
+
+    /// Course results of all years, newest first.
+    pub results: &'a [StudyResult],
+
+    /// Timetable changes found by syncs and not acknowledged yet.
+    pub changes: Vec<Change>,
+
+    /// Session keys of `changes`, highlighted in the table.
+    pub changed_sessions: HashSet<String>,
+
+    pub show_changes: bool,
+    /// First line of the changes overlay, a semester rollover lists hundreds.
+    pub changes_scroll: usize,
+
+    /// Overlapping sessions among the classes not ignored, refreshed every frame.
+    pub conflicts: Vec<Conflict<'a>>,
+
+    /// Session keys of `conflicts`, marked in the table.
+    pub conflicting_sessions: HashSet<String>,
+
+    /// Busy times from imported free/busy files, shown in the week view.
+    pub group_busy: Vec<(NaiveDateTime, NaiveDateTime)>,
+
+    /// Who the imported files are from.
+    pub group_names: Vec<String>,
+
+    /// Days all imported files cover, common free time is only shown there.
+    pub group_range: Option<(NaiveDate, NaiveDate)>,
+
+    pub travel: TravelConfig,
+
+    /// Classes too far from the previous one for the break, refreshed every frame.
+    pub travel_warnings: Vec<TravelWarning<'a>>,
+
+    /// What the place codes and rooms stand for.
+    pub places: Directory,
 }
 
 impl<'a> App<'a> {
@@ -67,7 +172,38 @@ impl<'a> App<'a> {
             search_query: None,
             show_clock: false,
             last_update: None,
-            offline_mode: false
+            offline_mode: false,
+            read_only: false,
+            reminders: None,
+            export_overlay_active: false,
+            export_overlay_index: 0,
+            export_message: None,
+            view: View::Table,
+            view_date: now.date(),
+            view_selected: None,
+            courses_group: None,
+            show_weekends: false,
+            details_course: None,
+            marked_days: HashSet::new(),
+            attendance: HashMap::new(),
+            attendance_config: AttendanceConfig::default(),
+            free_time: FreeTimeConfig::default(),
+            notes: Notes::default(),
+            note_editor: None,
+            exam_terms: Vec::new(),
+            results: &[],
+            changes: Vec::new(),
+            changed_sessions: HashSet::new(),
+            show_changes: false,
+            changes_scroll: 0,
+            conflicts: Vec::new(),
+            conflicting_sessions: HashSet::new(),
+            group_busy: Vec::new(),
+            group_names: Vec::new(),
+            group_range: None,
+            travel: TravelConfig::default(),
+            travel_warnings: Vec::new(),
+            places: Directory::default(),
         }
     }
 
@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+use std::fs;
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::cache::{cache_dir, cache_path};
+use crate::timetable::{parse_course_datetime, session_key, CourseAction};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Attendance {
+    Attended,
+    Missed,
+    Excused,
+}
+
+impl Attendance {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Attendance::Attended => "attended",
+            Attendance::Missed => "missed",
+            Attendance::Excused => "excused",
+        }
+    }
+}
+
+pub fn load_attendance() -> HashMap<String, Attendance> {
+    fs::read_to_string(cache_path("attendance.json"))
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_attendance(attendance: &HashMap<String, Attendance>) {
+    if fs::create_dir_all(cache_dir()).is_err() {
+        return;
+    }
+    if let Ok(data) = serde_json::to_string_pretty(attendance) {
+        let _ = fs::write(cache_path("attendance.json"), data);
+    }
+}
+
+/// Attendance of one course's sessions that already started.
+pub struct AttendanceStats {
+    pub attended: usize,
+    pub missed: usize,
+    pub excused: usize,
+    pub unmarked: usize,
+    pub limit: Option<u32>,
+}
+
+impl AttendanceStats {
+    pub fn compute(
+        sessions: &[&CourseAction],
+        attendance: &HashMap<String, Attendance>,
+        limit: Option<u32>,
+        now: NaiveDateTime,
+    ) -> Self {
+        let mut stats = AttendanceStats { attended: 0, missed: 0, excused: 0, unmarked: 0, limit };
+        for course in sessions {
+            if parse_course_datetime(course).is_none_or(|(start, _)| start > now) {
+                continue;
+            }
+            match session_key(course).and_then(|key| attendance.get(&key)) {
+                Some(Attendance::Attended) => stats.attended += 1,
+                Some(Attendance::Missed) => stats.missed += 1,
+                Some(Attendance::Excused) => stats.excused += 1,
+                None => stats.unmarked += 1,
+            }
+        }
+        stats
+    }
+
+    /// Absences still allowed, negative once the limit is exceeded. Excused ones do not count.
+    pub fn absences_left(&self) -> Option<i64> {
+        self.limit.map(|limit| limit as i64 - self.missed as i64)
+    }
+
+    /// Set when at most one more absence is allowed.
+    pub fn warning(&self) -> Option<String> {
+        match self.absences_left()? {
+            left if left < 0 => Some(format!("absence limit exceeded by {}", -left)),
+            0 => Some("no absences left".to_string()),
+            1 => Some("last absence left".to_string()),
+            _ => None,
+        }
+    }
+}
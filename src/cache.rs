@@ -0,0 +1,126 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::{Local, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+use crate::events::load_events;
+use crate::exams::registered_exam_actions;
+use crate::export::ExportedTimetable;
+use crate::timetable::{CourseAction, Timetable};
+
+#[derive(Serialize, Deserialize)]
+struct IgnoredIds {
+    ids: HashSet<u32>,
+}
+
+pub fn cache_dir() -> PathBuf {
+    let mut path = dirs::cache_dir().unwrap_or_else(|| PathBuf::from("/tmp"));
+    path.push("ujep_tui");
+    path
+}
+
+pub fn cache_path(file_name: &str) -> PathBuf {
+    cache_dir().join(file_name)
+}
+
+/// Notes problems a sync worked around to `sync.log` in the cache. Printing is no
+/// option while the TUI is on screen.
+pub fn log_sync_problem(message: &str) {
+    if fs::create_dir_all(cache_dir()).is_err() {
+        return;
+    }
+    if let Ok(mut file) = fs::OpenOptions::new().create(true).append(true).open(cache_path("sync.log")) {
+        let _ = writeln!(file, "{} {}", Local::now().format("%Y-%m-%d %H:%M:%S"), message);
+    }
+}
+
+pub fn load_ignored_ids() -> HashSet<u32> {
+    if let Ok(data) = fs::read_to_string(cache_path("ignored_ids.json")) {
+        if let Ok(ignored_ids) = serde_json::from_str::<IgnoredIds>(&data) {
+            return ignored_ids.ids;
+        }
+    }
+    HashSet::new()
+}
+
+pub fn save_ignored_ids(ignored_ids: &HashSet<u32>) {
+    let path = cache_dir();
+    if fs::create_dir_all(&path).is_err() {
+        return;
+    }
+    let ignored_ids = IgnoredIds {
+        ids: ignored_ids.clone(),
+    };
+    if let Ok(data) = serde_json::to_string(&ignored_ids) {
+        let _ = fs::write(path.join("ignored_ids.json"), data);
+    }
+}
+
+pub fn has_cached_timetable() -> bool {
+    cache_path("timetable.json").exists()
+}
+
+pub fn load_timetable() -> Result<Timetable, Box<dyn std::error::Error>> {
+    let path = cache_path("timetable.json");
+    let json_data = fs::read_to_string(&path)
+        .map_err(|e| format!("Cannot read cached timetable {}: {}", path.display(), e))?;
+    let mut timetable = parse_timetable(&json_data)?;
+    // Own events and registered exams are merged on every load, syncs only ever replace the cached file.
+    match load_events() {
+        Ok((events, skipped)) => {
+            for reason in skipped {
+                log_sync_problem(&format!("Skipped event, {}", reason));
+            }
+            for event in events {
+                timetable.data.courseActions.extend(event.course_actions());
+            }
+        }
+        Err(e) => log_sync_problem(&format!("No own events shown: {}", e)),
+    }
+    let exams = registered_exam_actions(&timetable.data.courseActions);
+    timetable.data.courseActions.extend(exams);
+    Ok(timetable)
+}
+
+/// Loads a timetable from any file the app produces or understands: the cached
+/// API response, a bare array of course actions or a normalized JSON export.
+pub fn load_timetable_file(path: &Path) -> Result<Timetable, Box<dyn std::error::Error>> {
+    let json_data = fs::read_to_string(path)
+        .map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    let json_data = strip_diacritics(&json_data);
+    if let Ok(timetable) = serde_json::from_str::<Timetable>(&json_data) {
+        Ok(timetable)
+    } else if let Ok(course_actions) = serde_json::from_str::<Vec<CourseAction>>(&json_data) {
+        Ok(Timetable::from_course_actions(course_actions, String::new()))
+    } else if let Ok(exported) = serde_json::from_str::<ExportedTimetable>(&json_data) {
+        Ok(exported.into_timetable())
+    } else {
+        Err(format!("{} is not a timetable file", path.display()).into())
+    }
+}
+
+pub fn parse_timetable(json_data: &str) -> Result<Timetable, Box<dyn std::error::Error>> {
+    Ok(serde_json::from_str(&strip_diacritics(json_data))?)
+}
+
+pub fn strip_diacritics(json_data: &str) -> String {
+    let replacements = [
+        ("Á", "A"), ("á", "a"), ("Č", "C"), ("č", "c"), ("Ď", "D"), ("ď", "d"),
+        ("É", "E"), ("é", "e"), ("Ě", "E"), ("ě", "e"), ("Í", "I"), ("í", "i"),
+        ("Ň", "N"), ("ň", "n"), ("Ó", "O"), ("ó", "o"), ("Ř", "R"), ("ř", "r"),
+        ("Š", "S"), ("š", "s"), ("Ť", "T"), ("ť", "t"), ("Ú", "U"), ("ú", "u"),
+        ("Ů", "U"), ("ů", "u"), ("Ý", "Y"), ("ý", "y"), ("Ž", "Z"), ("ž", "z")
+    ];
+    let mut json_data = json_data.to_string();
+    for &(from, to) in &replacements {
+        json_data = json_data.replace(from, to);
+    }
+    json_data
+}
+
+pub fn retrieved_at(timetable: &Timetable) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(&timetable.retrieved_at, "%Y-%m-%d %H:%M:%S%.f").ok()
+}
@@ -0,0 +1,278 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+
+use chrono::{Local, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+use crate::cache::{cache_dir, cache_path, parse_timetable};
+use crate::timetable::{parse_course_datetime, session_key, CourseAction};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Moved,
+    Relocated,
+}
+
+impl ChangeKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => "added",
+            ChangeKind::Removed => "cancelled",
+            ChangeKind::Moved => "moved",
+            ChangeKind::Relocated => "room changed",
+        }
+    }
+}
+
+/// When and where a session takes place.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Slot {
+    pub date: String,
+    pub from: String,
+    pub to: String,
+    pub place: String,
+    pub room: String,
+}
+
+impl Slot {
+    fn of(course: &CourseAction) -> Self {
+        Slot {
+            date: course.date.clone().unwrap_or_default(),
+            from: course.timeFrom.clone().unwrap_or_default(),
+            to: course.timeTo.clone().unwrap_or_default(),
+            place: course.place.clone().unwrap_or_default(),
+            room: course.room.clone().unwrap_or_default(),
+        }
+    }
+
+    pub fn start(&self) -> Option<NaiveDateTime> {
+        NaiveDateTime::parse_from_str(&format!("{} {}", self.date, self.from), "%d.%m.%Y %H:%M").ok()
+    }
+
+    pub fn time(&self) -> String {
+        format!("{} {}-{}", self.date, self.from, self.to)
+    }
+
+    pub fn location(&self) -> String {
+        format!("{} {}", self.place, self.room)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Change {
+    pub kind: ChangeKind,
+    /// Session key of the new session, of the old one for cancellations.
+    pub key: String,
+    pub abbr: String,
+    pub class_type: String,
+    pub name: String,
+    pub old: Option<Slot>,
+    pub new: Option<Slot>,
+    pub detected_at: String,
+}
+
+impl Change {
+    fn new(kind: ChangeKind, course: &CourseAction, old: Option<&CourseAction>, new: Option<&CourseAction>) -> Self {
+        Change {
+            kind,
+            key: session_key(course).unwrap_or_default(),
+            abbr: course.abbr.clone().unwrap_or_default(),
+            class_type: course.class_type.clone().unwrap_or_default(),
+            name: course.name.clone().unwrap_or_default(),
+            old: old.map(Slot::of),
+            new: new.map(Slot::of),
+            detected_at: Local::now().naive_local().format("%Y-%m-%d %H:%M").to_string(),
+        }
+    }
+
+    /// What changed, e.g. "21.10.2026 13:00-13:50 -> 22.10.2026 13:00-13:50" for a move.
+    pub fn summary(&self) -> String {
+        match (self.kind, &self.old, &self.new) {
+            (ChangeKind::Moved, Some(old), Some(new)) => format!("{} -> {}", old.time(), new.time()),
+            (ChangeKind::Relocated, Some(old), Some(new)) => {
+                format!("{} -> {} on {}", old.location(), new.location(), new.time())
+            }
+            (_, old, new) => new
+                .as_ref()
+                .or(old.as_ref())
+                .map_or(String::new(), |slot| format!("{} {}", slot.time(), slot.location())),
+        }
+    }
+}
+
+/// Differences between two downloads. Sessions are matched by action id and date;
+/// a session that only shows up on another date of the same action counts as moved.
+pub fn diff(old: &[CourseAction], new: &[CourseAction]) -> Vec<Change> {
+    let by_key = |courses: &[CourseAction]| -> HashMap<String, usize> {
+        courses.iter().enumerate().filter_map(|(i, c)| Some((session_key(c)?, i))).collect()
+    };
+    let (old_keys, new_keys) = (by_key(old), by_key(new));
+
+    let mut changes = Vec::new();
+    let mut removed: Vec<&CourseAction> = Vec::new();
+    for (key, &i) in &old_keys {
+        let before = &old[i];
+        match new_keys.get(key).map(|&j| &new[j]) {
+            Some(after) => {
+                if (&before.timeFrom, &before.timeTo) != (&after.timeFrom, &after.timeTo) {
+                    changes.push(Change::new(ChangeKind::Moved, after, Some(before), Some(after)));
+                }
+                if (&before.place, &before.room) != (&after.place, &after.room) {
+                    changes.push(Change::new(ChangeKind::Relocated, after, Some(before), Some(after)));
+                }
+            }
+            None => removed.push(before),
+        }
+    }
+    let mut added: Vec<&CourseAction> = new_keys
+        .iter()
+        .filter(|(key, _)| !old_keys.contains_key(*key))
+        .map(|(_, &j)| &new[j])
+        .collect();
+    removed.sort_by_key(|c| parse_course_datetime(c));
+    added.sort_by_key(|c| parse_course_datetime(c));
+
+    for before in removed {
+        match added.iter().position(|after| after.id == before.id) {
+            Some(j) => {
+                let after = added.remove(j);
+                changes.push(Change::new(ChangeKind::Moved, after, Some(before), Some(after)));
+                if (&before.place, &before.room) != (&after.place, &after.room) {
+                    changes.push(Change::new(ChangeKind::Relocated, after, Some(before), Some(after)));
+                }
+            }
+            None => changes.push(Change::new(ChangeKind::Removed, before, Some(before), None)),
+        }
+    }
+    for after in added {
+        changes.push(Change::new(ChangeKind::Added, after, None, Some(after)));
+    }
+    changes.sort_by_key(|change| change.new.as_ref().or(change.old.as_ref()).and_then(Slot::start));
+    changes
+}
+
+/// Changes not acknowledged yet, in the order they were found.
+pub fn load_changes() -> Vec<Change> {
+    fs::read_to_string(cache_path("changes.json"))
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_changes(changes: &[Change]) {
+    if fs::create_dir_all(cache_dir()).is_err() {
+        return;
+    }
+    if let Ok(data) = serde_json::to_string_pretty(changes) {
+        let _ = fs::write(cache_path("changes.json"), data);
+    }
+}
+
+/// Diffs a fresh download against the previous one and adds the result to the
+/// pending changes, a newer change of a session replaces the older one.
+pub fn record_changes(previous: &str, fresh: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let (previous, fresh) = (parse_timetable(previous)?, parse_timetable(fresh)?);
+    let found = diff(&previous.data.courseActions, &fresh.data.courseActions);
+    if found.is_empty() {
+        return Ok(());
+    }
+    let mut changes = load_changes();
+    changes.retain(|old| !found.iter().any(|new| new.key == old.key && new.kind == old.kind));
+    changes.extend(found);
+    save_changes(&changes);
+    Ok(())
+}
+
+/// Sessions to highlight in the table.
+pub fn changed_sessions(changes: &[Change]) -> HashSet<String> {
+    changes
+        .iter()
+        .filter(|change| change.kind != ChangeKind::Removed)
+        .map(|change| change.key.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timetable::test_course;
+
+    fn kinds(changes: &[Change]) -> Vec<(ChangeKind, &str)> {
+        changes.iter().map(|change| (change.kind, change.key.as_str())).collect()
+    }
+
+    fn in_room(mut course: CourseAction, place: &str, room: &str) -> CourseAction {
+        course.place = Some(place.to_string());
+        course.room = Some(room.to_string());
+        course
+    }
+
+    #[test]
+    fn unchanged_timetable_has_no_changes() {
+        let old = vec![in_room(test_course(1, "21.10.2026", "13:00", "13:50"), "CP", "6.13")];
+        assert!(diff(&old, &old.clone()).is_empty());
+    }
+
+    #[test]
+    fn time_shift_on_the_same_date_is_a_move() {
+        let old = vec![test_course(1, "21.10.2026", "13:00", "13:50")];
+        let new = vec![test_course(1, "21.10.2026", "14:00", "14:50")];
+        let changes = diff(&old, &new);
+        assert_eq!(kinds(&changes), vec![(ChangeKind::Moved, "1-20261021")]);
+        assert_eq!(changes[0].summary(), "21.10.2026 13:00-13:50 -> 21.10.2026 14:00-14:50");
+    }
+
+    #[test]
+    fn same_id_on_another_date_is_a_move() {
+        let old = vec![test_course(1, "21.10.2026", "13:00", "13:50")];
+        let new = vec![test_course(1, "23.10.2026", "13:00", "13:50")];
+        let changes = diff(&old, &new);
+        assert_eq!(kinds(&changes), vec![(ChangeKind::Moved, "1-20261023")]);
+        assert_eq!(changes[0].old.as_ref().unwrap().date, "21.10.2026");
+    }
+
+    #[test]
+    fn room_change_is_a_relocation() {
+        let old = vec![in_room(test_course(1, "21.10.2026", "13:00", "13:50"), "CP", "6.13")];
+        let new = vec![in_room(test_course(1, "21.10.2026", "13:00", "13:50"), "MF", "2.28")];
+        let changes = diff(&old, &new);
+        assert_eq!(kinds(&changes), vec![(ChangeKind::Relocated, "1-20261021")]);
+        assert_eq!(changes[0].summary(), "CP 6.13 -> MF 2.28 on 21.10.2026 13:00-13:50");
+    }
+
+    #[test]
+    fn missing_session_is_cancelled() {
+        let old = vec![test_course(1, "21.10.2026", "13:00", "13:50"), test_course(2, "22.10.2026", "10:00", "11:50")];
+        let new = vec![test_course(2, "22.10.2026", "10:00", "11:50")];
+        let changes = diff(&old, &new);
+        assert_eq!(kinds(&changes), vec![(ChangeKind::Removed, "1-20261021")]);
+        assert!(changes[0].new.is_none());
+    }
+
+    #[test]
+    fn new_session_is_added() {
+        let old = vec![test_course(1, "21.10.2026", "13:00", "13:50")];
+        let new = vec![test_course(1, "21.10.2026", "13:00", "13:50"), test_course(2, "22.10.2026", "10:00", "11:50")];
+        let changes = diff(&old, &new);
+        assert_eq!(kinds(&changes), vec![(ChangeKind::Added, "2-20261022")]);
+        assert!(changes[0].old.is_none());
+    }
+
+    #[test]
+    fn move_with_room_change_is_both() {
+        let old = vec![in_room(test_course(1, "21.10.2026", "13:00", "13:50"), "CP", "6.13")];
+        let same_day = vec![in_room(test_course(1, "21.10.2026", "15:00", "15:50"), "CP", "1.01")];
+        assert_eq!(
+            kinds(&diff(&old, &same_day)),
+            vec![(ChangeKind::Moved, "1-20261021"), (ChangeKind::Relocated, "1-20261021")]
+        );
+        let other_day = vec![in_room(test_course(1, "22.10.2026", "13:00", "13:50"), "MF", "2.28")];
+        assert_eq!(
+            kinds(&diff(&old, &other_day)),
+            vec![(ChangeKind::Moved, "1-20261022"), (ChangeKind::Relocated, "1-20261022")]
+        );
+    }
+}
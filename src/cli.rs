@@ -0,0 +1,411 @@
+use std::path::PathBuf;
+
+use chrono::NaiveDate;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+use crate::events::{parse_time, Repeat};
+
+/// UJEP timetable client - lightning fast access & filter.
+#[derive(Parser)]
+#[command(name = "ujep_tui", version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Run the full-screen timetable viewer (default)
+    Tui(TuiArgs),
+    /// Log in if needed and download a fresh timetable into the cache
+    Sync,
+    /// Log in with STAG credentials and cache the access token
+    Login,
+    /// Forget the cached access token and profile
+    Logout,
+    /// Print classes from the cached timetable
+    List(ListArgs),
+    /// Print classes grouped by day, e.g. for MOTDs and cron mails
+    Agenda(AgendaArgs),
+    /// Print the ongoing or next class, e.g. for status bars
+    Next(NextArgs),
+    /// Export the cached timetable
+    Export(ExportArgs),
+    /// Manage the list of ignored classes
+    Ignore(IgnoreArgs),
+    /// Manage own events shown alongside the classes
+    Event(EventArgs),
+    /// Print upcoming exam terms from the last sync
+    Exams(ExamsArgs),
+    /// Print course results, credits and the grade average from the last sync
+    Results,
+    /// List the stored timetable snapshots, one per sync that changed something
+    History,
+    /// Print what changed between two timetable snapshots
+    Diff(DiffArgs),
+    /// Print free time between classes, e.g. to plan study sessions
+    Free(FreeArgs),
+    /// Share free/busy files without course details and find common free time
+    Freebusy(FreeBusyArgs),
+    /// Look up buildings and rooms, e.g. `places CP` or `places lab`
+    Places(PlacesArgs),
+    /// Print overlapping classes of the coming days, exits with 1 when there are any
+    Conflicts(ConflictsArgs),
+    /// Run the reminder hook before each class, without the TUI
+    Remind(RemindArgs),
+    /// Keep syncing in the background and publish the current/next class
+    /// to a state file and a Unix socket
+    Daemon(DaemonArgs),
+}
+
+#[derive(Args, Default)]
+pub struct TuiArgs {
+    /// View this timetable file read-only, without login or network.
+    /// Accepts the cached API response, a plain array of course actions
+    /// or a normalized JSON export.
+    #[arg(short, long)]
+    pub file: Option<PathBuf>,
+    /// Free/busy file of someone else, repeatable. The week view then shows
+    /// their busy times and the free time you have in common.
+    #[arg(long = "freebusy")]
+    pub free_busy: Vec<PathBuf>,
+    /// Colour theme for this run instead of the configured one, e.g. "light"
+    #[arg(long)]
+    pub theme: Option<String>,
+}
+
+#[derive(Args)]
+pub struct ListArgs {
+    /// Include classes that have already ended
+    #[arg(short, long)]
+    pub all: bool,
+    /// Include ignored classes
+    #[arg(long)]
+    pub show_ignored: bool,
+    /// Filter like the TUI search, e.g. "ops" or "day:po"
+    #[arg(short, long)]
+    pub search: Option<String>,
+    /// Print at most this many classes
+    #[arg(short = 'n', long)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Args)]
+pub struct AgendaArgs {
+    /// Classes of today (default)
+    #[arg(long, group = "range")]
+    pub today: bool,
+    /// Classes of tomorrow
+    #[arg(long, group = "range")]
+    pub tomorrow: bool,
+    /// Classes from Monday to Sunday of this week
+    #[arg(long, group = "range")]
+    pub week: bool,
+    /// First day to print (YYYY-MM-DD or D.M.YYYY)
+    #[arg(long, value_parser = parse_date_arg, group = "range")]
+    pub from: Option<NaiveDate>,
+    /// Last day to print, defaults to --from
+    #[arg(long, value_parser = parse_date_arg, requires = "from")]
+    pub to: Option<NaiveDate>,
+    /// Filter like the TUI search, e.g. "ops" or "type:cv"
+    #[arg(short, long)]
+    pub search: Option<String>,
+    /// Include ignored classes
+    #[arg(long)]
+    pub show_ignored: bool,
+    /// Output layout
+    #[arg(long, value_enum, default_value_t = AgendaStyle::Table)]
+    pub style: AgendaStyle,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum AgendaStyle {
+    /// Aligned columns with a header per day
+    Table,
+    /// One short line per class
+    Compact,
+}
+
+pub fn parse_date_arg(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(value, "%d.%m.%Y"))
+        .map_err(|_| format!("invalid date '{}', expected YYYY-MM-DD or D.M.YYYY", value))
+}
+
+#[derive(Args)]
+pub struct NextArgs {
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = NextFormat::Text)]
+    pub format: NextFormat,
+    /// Text template with {abbr} {name} {dept} {type} {place} {room} {date} {from} {to}
+    /// {in} {left} {when} {state} {progress} placeholders
+    #[arg(short, long, default_value = "{abbr} {when} – {place} {room}")]
+    pub template: String,
+    /// Text printed when there is no upcoming class
+    #[arg(long, default_value = "No upcoming classes.")]
+    pub empty: String,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum NextFormat {
+    /// The template filled in
+    Text,
+    /// All status fields as a JSON object
+    Json,
+    /// Waybar custom module JSON (text, tooltip, class, percentage)
+    Waybar,
+}
+
+#[derive(Args)]
+pub struct ExportArgs {
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = ExportFormat::Raw)]
+    pub format: ExportFormat,
+    /// Write to this file instead of stdout
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+    /// Keep ignored classes in the export (not for raw)
+    #[arg(long)]
+    pub include_ignored: bool,
+    /// Filter like the TUI search, e.g. "ops" or "type:cv" (not for raw)
+    #[arg(short, long)]
+    pub search: Option<String>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ExportFormat {
+    /// The cached API response as-is
+    Raw,
+    /// iCalendar (.ics) for calendar apps
+    Ics,
+    /// One row per class with stable column names and ISO datetimes
+    Csv,
+    /// Normalized JSON with stable field names and ISO datetimes
+    Json,
+}
+
+#[derive(Args)]
+pub struct RemindArgs {
+    /// Minutes before the class to fire, repeatable (overrides the config)
+    #[arg(long = "offset")]
+    pub offsets: Vec<i64>,
+    /// Shell command to run (overrides the config), e.g.
+    /// 'notify-send "$UJEP_ABBR" {message}'
+    #[arg(short, long)]
+    pub command: Option<String>,
+    /// Seconds between checks
+    #[arg(long, default_value_t = 30)]
+    pub interval: u64,
+    /// Check once and exit, e.g. from cron
+    #[arg(long)]
+    pub once: bool,
+}
+
+#[derive(Args)]
+pub struct DaemonArgs {
+    /// Seconds between state updates
+    #[arg(long, default_value_t = 5)]
+    pub interval: u64,
+    /// Minutes between syncs with the server, 0 to only watch the cache
+    #[arg(long, default_value_t = 60)]
+    pub sync_interval: u64,
+    /// State file, defaults to state.json in the cache directory
+    #[arg(long)]
+    pub state_file: Option<PathBuf>,
+    /// Socket path, defaults to $XDG_RUNTIME_DIR/ujep_tui.sock
+    #[arg(long)]
+    pub socket: Option<PathBuf>,
+    /// Only write the state file
+    #[arg(long)]
+    pub no_socket: bool,
+}
+
+#[derive(Args)]
+pub struct IgnoreArgs {
+    #[command(subcommand)]
+    pub action: IgnoreAction,
+}
+
+#[derive(Subcommand)]
+pub enum IgnoreAction {
+    /// Show ignored classes
+    List,
+    /// Ignore classes by id
+    Add {
+        #[arg(required = true)]
+        ids: Vec<u32>,
+    },
+    /// Stop ignoring classes by id
+    Remove {
+        #[arg(required = true)]
+        ids: Vec<u32>,
+    },
+    /// Stop ignoring all classes
+    Clear,
+}
+
+#[derive(Args)]
+pub struct DiffArgs {
+    /// Older snapshot, a timestamp or its start like 2026-10-12 (defaults to the second newest)
+    pub old: Option<String>,
+    /// Newer snapshot (defaults to the newest)
+    pub new: Option<String>,
+}
+
+#[derive(Args)]
+pub struct FreeArgs {
+    /// First day (YYYY-MM-DD or D.M.YYYY), defaults to today
+    #[arg(long, value_parser = parse_date_arg)]
+    pub from: Option<NaiveDate>,
+    /// Last day, defaults to a week from --from
+    #[arg(long, value_parser = parse_date_arg)]
+    pub to: Option<NaiveDate>,
+    /// Start of the day (HH:MM), overrides the config
+    #[arg(long, value_parser = parse_time_arg)]
+    pub day_start: Option<String>,
+    /// End of the day (HH:MM), overrides the config
+    #[arg(long, value_parser = parse_time_arg)]
+    pub day_end: Option<String>,
+    /// Shortest gap to list in minutes, overrides the config
+    #[arg(short, long)]
+    pub min: Option<i64>,
+    /// Include Saturdays and Sundays
+    #[arg(long)]
+    pub weekends: bool,
+    /// Count ignored classes as busy
+    #[arg(long)]
+    pub show_ignored: bool,
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = FreeFormat::Text)]
+    pub format: FreeFormat,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum FreeFormat {
+    /// One line per free slot
+    Text,
+    /// An array of {date, start, end, minutes} objects with ISO datetimes
+    Json,
+}
+
+#[derive(Args)]
+pub struct FreeBusyArgs {
+    #[command(subcommand)]
+    pub action: FreeBusyAction,
+}
+
+#[derive(Subcommand)]
+pub enum FreeBusyAction {
+    /// Write your busy times, no course names or rooms, for others to import
+    Export(FreeBusyExportArgs),
+    /// Print the free time you share with the people of the given files
+    Common(FreeBusyCommonArgs),
+}
+
+#[derive(Args)]
+pub struct FreeBusyExportArgs {
+    /// First day (YYYY-MM-DD or D.M.YYYY), defaults to today
+    #[arg(long, value_parser = parse_date_arg)]
+    pub from: Option<NaiveDate>,
+    /// Last day, defaults to four weeks from --from
+    #[arg(long, value_parser = parse_date_arg)]
+    pub to: Option<NaiveDate>,
+    /// Your name as the others will see it
+    #[arg(short, long)]
+    pub name: Option<String>,
+    /// Write to this file instead of stdout
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+    /// Count ignored classes as busy
+    #[arg(long)]
+    pub show_ignored: bool,
+}
+
+#[derive(Args)]
+pub struct FreeBusyCommonArgs {
+    /// Free/busy files from `freebusy export`
+    #[arg(required = true)]
+    pub files: Vec<PathBuf>,
+    /// Only overlay the files, leave your own timetable out
+    #[arg(long)]
+    pub without_me: bool,
+    #[command(flatten)]
+    pub free: FreeArgs,
+}
+
+#[derive(Args)]
+pub struct PlacesArgs {
+    /// Part of a place code, room, name, address or description, all places without
+    pub query: Option<String>,
+}
+
+#[derive(Args)]
+pub struct ConflictsArgs {
+    /// How many days ahead to check, starting now
+    #[arg(short, long, default_value_t = 14)]
+    pub days: i64,
+    /// Include ignored classes
+    #[arg(long)]
+    pub show_ignored: bool,
+}
+
+#[derive(Args)]
+pub struct ExamsArgs {
+    /// Only terms you are registered for
+    #[arg(short, long)]
+    pub registered: bool,
+}
+
+#[derive(Args)]
+pub struct EventArgs {
+    #[command(subcommand)]
+    pub action: EventAction,
+}
+
+#[derive(Subcommand)]
+pub enum EventAction {
+    /// Show own events
+    List,
+    /// Add an event, e.g. --title "Study group" --date 2026-10-20 --from 16:00 --to 18:00
+    Add(EventAddArgs),
+    /// Remove events by id
+    Remove {
+        #[arg(required = true)]
+        ids: Vec<u32>,
+    },
+}
+
+fn parse_time_arg(value: &str) -> Result<String, String> {
+    parse_time(value)
+        .map(|time| time.format("%H:%M").to_string())
+        .ok_or_else(|| format!("invalid time '{}', expected HH:MM", value))
+}
+
+#[derive(Args)]
+pub struct EventAddArgs {
+    #[arg(long)]
+    pub title: String,
+    /// First day (YYYY-MM-DD or D.M.YYYY)
+    #[arg(long, value_parser = parse_date_arg)]
+    pub date: NaiveDate,
+    /// Start time, HH:MM
+    #[arg(long, value_parser = parse_time_arg)]
+    pub from: String,
+    /// End time, HH:MM
+    #[arg(long, value_parser = parse_time_arg)]
+    pub to: String,
+    #[arg(long)]
+    pub place: Option<String>,
+    #[arg(long)]
+    pub room: Option<String>,
+    /// Shown as the class type, e.g. exam, job or consultation
+    #[arg(long)]
+    pub kind: Option<String>,
+    #[arg(long)]
+    pub note: Option<String>,
+    #[arg(long, value_enum)]
+    pub repeat: Option<Repeat>,
+    /// Last day of a repeating event, defaults to half a year
+    #[arg(long, value_parser = parse_date_arg, requires = "repeat")]
+    pub until: Option<NaiveDate>,
+}
@@ -0,0 +1,697 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime};
+use serde_json::json;
+use crossterm::{
+    execute,
+    terminal::{disable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+
+use crate::cache::{cache_path, load_ignored_ids, load_timetable, parse_timetable, retrieved_at, save_ignored_ids};
+use crate::cli::{
+    AgendaArgs, AgendaStyle, ConflictsArgs, DiffArgs, EventAction, EventArgs, ExamsArgs, ExportArgs, ExportFormat, FreeArgs, FreeBusyAction, FreeBusyArgs, FreeFormat, IgnoreAction, IgnoreArgs, ListArgs,
+    NextArgs, NextFormat, PlacesArgs, RemindArgs,
+};
+use crate::changes::diff as diff_timetables;
+use crate::config::{config_path, load_config, FreeTimeConfig};
+use crate::conflicts::find_conflicts;
+use crate::events::{events_path, load_events, parse_time, save_events, UserEvent, MAX_EVENT_ID};
+use crate::exams::load_exam_terms;
+use crate::export::{to_csv, to_ics, to_json};
+use crate::free::{course_times, free_slots, FreeSlot};
+use crate::freebusy::{common_range, load_free_busy, FreeBusy};
+use crate::fetch_timetable::fetch_timetable;
+use crate::history::{find_snapshot, history_dir, list_snapshots, read_snapshot, Snapshot};
+use crate::login::run_login;
+use crate::reminders::Reminders;
+use crate::results::{load_results, ResultsSummary};
+use crate::notes::Notes;
+use crate::places::Directory;
+use crate::search::matches_query;
+use crate::status::{format_short_duration, ClassStatus};
+use crate::theme::{set_theme, Theme};
+use crate::timetable::{is_course_ongoing, parse_course_datetime, weekday_abbr, CourseAction, Timetable};
+
+/// Dated courses sorted by start, optionally without the ignored ones.
+pub fn sorted_courses<'a>(
+    timetable: &'a Timetable,
+    ignored_ids: Option<&HashSet<u32>>,
+) -> Vec<&'a CourseAction> {
+    let mut courses: Vec<&CourseAction> = timetable
+        .data
+        .courseActions
+        .iter()
+        .filter(|c| c.date.is_some())
+        .filter(|c| match (ignored_ids, c.id) {
+            (Some(ignored), Some(id)) => !ignored.contains(&id),
+            _ => true,
+        })
+        .collect();
+    courses.sort_by_key(|c| parse_course_datetime(c).map(|(start, _)| start));
+    courses
+}
+
+async fn interactive_login() -> Result<(), Box<dyn std::error::Error>> {
+    // The credential prompt draws a full-screen form, keep it off the shell scrollback.
+    let tty = io::stdout().is_terminal();
+    // A broken config is reported by the commands that need it, the form then stays dark.
+    if let Some(theme) = load_config().ok().and_then(|config| Theme::from_config(&config.theme, None).ok()) {
+        set_theme(theme);
+    }
+    if tty {
+        execute!(io::stdout(), EnterAlternateScreen)?;
+    }
+    let res = run_login().await;
+    if tty {
+        disable_raw_mode()?;
+        execute!(io::stdout(), LeaveAlternateScreen)?;
+    }
+    res
+}
+
+pub async fn login() -> Result<(), Box<dyn std::error::Error>> {
+    interactive_login().await?;
+    println!("Logged in.");
+    Ok(())
+}
+
+pub fn logout() -> Result<(), Box<dyn std::error::Error>> {
+    for file_name in ["bearer", "profile.json"] {
+        let path = cache_path(file_name);
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+    }
+    println!("Logged out.");
+    Ok(())
+}
+
+pub async fn sync() -> Result<(), Box<dyn std::error::Error>> {
+    interactive_login().await?;
+    fetch_timetable().await?;
+    let timetable = load_timetable()?;
+    println!(
+        "Timetable synced, {} classes cached.",
+        sorted_courses(&timetable, None).len()
+    );
+    Ok(())
+}
+
+fn format_course_line(course: &CourseAction, now: NaiveDateTime) -> String {
+    let indicator = if is_course_ongoing(course, now) { "*" } else { " " };
+    format!(
+        "{} {:<3} {:<11} {:>5}-{:<5} {:<3} {:<40} {:<3} {}",
+        indicator,
+        course.day.as_deref().unwrap_or(""),
+        course.date.as_deref().unwrap_or("N/A"),
+        course.timeFrom.as_deref().unwrap_or("N/A"),
+        course.timeTo.as_deref().unwrap_or("N/A"),
+        course.class_type.as_deref().unwrap_or("N/A"),
+        course.name.as_deref().unwrap_or("N/A"),
+        course.place.as_deref().unwrap_or("N/A"),
+        course.room.as_deref().unwrap_or("N/A"),
+    )
+}
+
+pub fn list(args: &ListArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let timetable = load_timetable()?;
+    let ignored_ids = load_ignored_ids();
+    let notes = Notes::load();
+    let now = Local::now().naive_local();
+
+    let courses = sorted_courses(&timetable, (!args.show_ignored).then_some(&ignored_ids));
+    let shown = courses
+        .into_iter()
+        .filter(|c| args.all || parse_course_datetime(c).is_none_or(|(_, end)| end >= now))
+        .filter(|c| args.search.as_deref().is_none_or(|q| matches_query(c, notes.text(c).as_deref(), q)))
+        .take(args.limit.unwrap_or(usize::MAX));
+
+    let mut stdout = io::stdout().lock();
+    for course in shown {
+        writeln!(stdout, "{}", format_course_line(course, now))?;
+    }
+    Ok(())
+}
+
+pub fn agenda(args: &AgendaArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let timetable = load_timetable()?;
+    let ignored_ids = load_ignored_ids();
+    let notes = Notes::load();
+    let now = Local::now().naive_local();
+    let today = now.date();
+
+    let (from, to) = if args.tomorrow {
+        let tomorrow = today + Duration::days(1);
+        (tomorrow, tomorrow)
+    } else if args.week {
+        let monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+        (monday, monday + Duration::days(6))
+    } else if let Some(from) = args.from {
+        (from, args.to.unwrap_or(from))
+    } else {
+        (today, today)
+    };
+
+    let courses: Vec<&CourseAction> = sorted_courses(&timetable, (!args.show_ignored).then_some(&ignored_ids))
+        .into_iter()
+        .filter(|c| parse_course_datetime(c).is_some_and(|(start, _)| start.date() >= from && start.date() <= to))
+        .filter(|c| args.search.as_deref().is_none_or(|q| matches_query(c, notes.text(c).as_deref(), q)))
+        .collect();
+
+    let mut stdout = io::stdout().lock();
+    if courses.is_empty() {
+        writeln!(stdout, "No classes.")?;
+        return Ok(());
+    }
+
+    let name_width = courses
+        .iter()
+        .map(|c| c.name.as_deref().unwrap_or("N/A").chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut current_day = None;
+    for course in courses {
+        let (start, _) = parse_course_datetime(course).unwrap();
+        if current_day != Some(start.date()) {
+            if current_day.is_some() {
+                writeln!(stdout)?;
+            }
+            current_day = Some(start.date());
+            writeln!(stdout, "{} {}", weekday_abbr(start.date()), start.format("%-d.%-m.%Y"))?;
+            if let AgendaStyle::Table = args.style {
+                writeln!(
+                    stdout,
+                    "  {:<11} {:<4} {:<name_width$} {:<5} Room",
+                    "Time", "Type", "Course", "Place"
+                )?;
+            }
+        }
+        let marker = if is_course_ongoing(course, now) { "*" } else { " " };
+        match args.style {
+            AgendaStyle::Table => writeln!(
+                stdout,
+                "{} {:>5}-{:<5} {:<4} {:<name_width$} {:<5} {}",
+                marker,
+                course.timeFrom.as_deref().unwrap_or("N/A"),
+                course.timeTo.as_deref().unwrap_or("N/A"),
+                course.class_type.as_deref().unwrap_or("N/A"),
+                course.name.as_deref().unwrap_or("N/A"),
+                course.place.as_deref().unwrap_or("N/A"),
+                course.room.as_deref().unwrap_or("N/A"),
+            )?,
+            AgendaStyle::Compact => writeln!(
+                stdout,
+                "{}{} {} {} {} {}",
+                marker,
+                course.timeFrom.as_deref().unwrap_or("N/A"),
+                course.abbr.as_deref().unwrap_or("N/A"),
+                course.class_type.as_deref().unwrap_or("N/A"),
+                course.place.as_deref().unwrap_or("N/A"),
+                course.room.as_deref().unwrap_or("N/A"),
+            )?,
+        }
+    }
+    Ok(())
+}
+
+pub fn next(args: &NextArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let timetable = load_timetable()?;
+    let ignored_ids = load_ignored_ids();
+    let now = Local::now().naive_local();
+    let courses = sorted_courses(&timetable, Some(&ignored_ids));
+    let status = ClassStatus::compute(&courses, now);
+
+    let text = match status {
+        ClassStatus::None => args.empty.clone(),
+        _ => status.render(&args.template, now),
+    };
+
+    match args.format {
+        NextFormat::Text => println!("{}", text),
+        NextFormat::Json => println!("{}", status.to_json(now)),
+        NextFormat::Waybar => {
+            let tooltip = status.course().map_or(String::new(), |(course, _, _)| {
+                format!(
+                    "{} ({})\n{} {}-{}\n{} {}",
+                    course.name.as_deref().unwrap_or("N/A"),
+                    course.class_type.as_deref().unwrap_or("N/A"),
+                    course.date.as_deref().unwrap_or("N/A"),
+                    course.timeFrom.as_deref().unwrap_or("N/A"),
+                    course.timeTo.as_deref().unwrap_or("N/A"),
+                    course.place.as_deref().unwrap_or("N/A"),
+                    course.room.as_deref().unwrap_or("N/A"),
+                )
+            });
+            let waybar = json!({
+                "text": text,
+                "alt": status.state(),
+                "tooltip": tooltip,
+                "class": status.state(),
+                "percentage": (status.progress() * 100.0).round() as u8,
+            });
+            println!("{}", waybar);
+        }
+    }
+    Ok(())
+}
+
+pub fn export(args: &ExportArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let data = match args.format {
+        // The cached response is passed through untouched, filters cannot apply.
+        ExportFormat::Raw if args.search.is_some() || args.include_ignored => {
+            return Err("--search and --include-ignored need --format ics, csv or json, raw is the cached response as-is".into());
+        }
+        ExportFormat::Raw => fs::read_to_string(cache_path("timetable.json"))
+            .map_err(|e| format!("Cannot read cached timetable: {}", e))?,
+        format => {
+            let timetable = load_timetable()?;
+            let ignored_ids = load_ignored_ids();
+            let notes = Notes::load();
+            let courses: Vec<&CourseAction> = sorted_courses(&timetable, (!args.include_ignored).then_some(&ignored_ids))
+                .into_iter()
+                .filter(|c| args.search.as_deref().is_none_or(|q| matches_query(c, notes.text(c).as_deref(), q)))
+                .collect();
+            match format {
+                ExportFormat::Csv => to_csv(&courses, &notes),
+                ExportFormat::Json => to_json(&courses, &notes, retrieved_at(&timetable))?,
+                _ => to_ics(&courses, &notes, retrieved_at(&timetable)),
+            }
+        }
+    };
+    match &args.output {
+        Some(path) => fs::write(path, data)?,
+        None => io::stdout().lock().write_all(data.as_bytes())?,
+    }
+    Ok(())
+}
+
+pub fn ignore(args: &IgnoreArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let mut ignored_ids = load_ignored_ids();
+    match &args.action {
+        IgnoreAction::List => {
+            let timetable = load_timetable().ok();
+            let mut ids: Vec<&u32> = ignored_ids.iter().collect();
+            ids.sort();
+            for id in ids {
+                let course = timetable
+                    .as_ref()
+                    .and_then(|t| t.data.courseActions.iter().find(|c| c.id == Some(*id)));
+                match course {
+                    Some(course) => println!(
+                        "{:<8} {}[{}]",
+                        id,
+                        course.name.as_deref().unwrap_or("N/A"),
+                        course.class_type.as_deref().unwrap_or("N/A")
+                    ),
+                    None => println!("{}", id),
+                }
+            }
+            return Ok(());
+        }
+        IgnoreAction::Add { ids } => ignored_ids.extend(ids),
+        IgnoreAction::Remove { ids } => ignored_ids.retain(|id| !ids.contains(id)),
+        IgnoreAction::Clear => ignored_ids.clear(),
+    }
+    save_ignored_ids(&ignored_ids);
+    Ok(())
+}
+
+pub fn event(args: &EventArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let (mut events, skipped) = load_events()?;
+    match &args.action {
+        EventAction::List => {
+            for reason in &skipped {
+                eprintln!("Skipped {}", reason);
+            }
+            for event in &events {
+                let repeat = match (event.repeat, event.dates().last()) {
+                    (Some(repeat), Some(last)) => format!(
+                        " {} until {}",
+                        repeat.label(),
+                        last.format("%-d.%-m.%Y")
+                    ),
+                    _ => String::new(),
+                };
+                println!(
+                    "{:<4} {} {}-{}{}  {}[{}] {} {}",
+                    event.id,
+                    event.date.format("%-d.%-m.%Y"),
+                    event.from,
+                    event.to,
+                    repeat,
+                    event.title,
+                    event.kind.as_deref().unwrap_or("Event"),
+                    event.place.as_deref().unwrap_or(""),
+                    event.room.as_deref().unwrap_or("")
+                );
+            }
+            return Ok(());
+        }
+        EventAction::Add(add) => {
+            if parse_time(&add.to) <= parse_time(&add.from) {
+                return Err("The event has to end after it starts".into());
+            }
+            let id = events.iter().map(|e| e.id).max().map_or(1, |id| id + 1);
+            if id >= MAX_EVENT_ID {
+                return Err(format!("No event ids left below {}, renumber {}", MAX_EVENT_ID, events_path().display()).into());
+            }
+            events.push(UserEvent {
+                id,
+                title: add.title.clone(),
+                kind: add.kind.clone(),
+                date: add.date,
+                from: add.from.clone(),
+                to: add.to.clone(),
+                place: add.place.clone(),
+                room: add.room.clone(),
+                note: add.note.clone(),
+                repeat: add.repeat,
+                until: add.until,
+            });
+            println!("Added event {}", id);
+        }
+        EventAction::Remove { ids } => {
+            let count = events.len();
+            events.retain(|e| !ids.contains(&e.id));
+            if events.len() == count {
+                return Err("No such event".into());
+            }
+        }
+    }
+    if !skipped.is_empty() {
+        // Saving would drop the skipped entries for good.
+        return Err(format!("Not saving, fix or remove the broken entries first: {}", skipped.join("; ")).into());
+    }
+    save_events(&events)
+}
+
+pub fn exams(args: &ExamsArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let now = Local::now().naive_local();
+    for term in load_exam_terms() {
+        if term.end < now || (args.registered && !term.registered) {
+            continue;
+        }
+        println!(
+            "{:<10} {} {} {}-{}  {}/{} {}  {} {}  {} {}",
+            term.status(),
+            weekday_abbr(term.start.date()),
+            term.start.format("%-d.%-m.%Y"),
+            term.start.format("%H:%M"),
+            term.end.format("%H:%M"),
+            term.dept,
+            term.abbr,
+            term.kind,
+            term.place,
+            term.room,
+            term.teacher,
+            term.capacity()
+        );
+    }
+    Ok(())
+}
+
+pub fn results() -> Result<(), Box<dyn std::error::Error>> {
+    let results = load_results();
+    for result in &results {
+        println!(
+            "{:<5} {:<2} {:<10} {:<40} {:>2} {:<12} {:<2} {:<10} {}",
+            result.year,
+            result.semester,
+            format!("{}/{}", result.dept, result.abbr),
+            result.name,
+            result.credits.map_or(String::new(), |credits| credits.to_string()),
+            result.kind,
+            result.grade.as_deref().unwrap_or(""),
+            result.date.map_or(String::new(), |date| date.format("%-d.%-m.%Y").to_string()),
+            result.status()
+        );
+    }
+    println!("{}", ResultsSummary::compute(&results).text());
+    Ok(())
+}
+
+pub fn history() -> Result<(), Box<dyn std::error::Error>> {
+    for snapshot in list_snapshots(&history_dir()) {
+        let classes = read_snapshot(&snapshot)
+            .ok()
+            .and_then(|data| parse_timetable(&data).ok())
+            .map_or(0, |timetable| sorted_courses(&timetable, None).len());
+        println!("{}  {}  {} classes", snapshot.name, snapshot.taken_at.format("%-d.%-m.%Y %H:%M:%S"), classes);
+    }
+    Ok(())
+}
+
+pub fn diff(args: &DiffArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let snapshots = list_snapshots(&history_dir());
+    let pick = |query: &Option<String>, back: usize| -> Result<&Snapshot, Box<dyn std::error::Error>> {
+        match query {
+            Some(query) => find_snapshot(&snapshots, query).ok_or_else(|| format!("No snapshot matches '{}'", query).into()),
+            None => snapshots
+                .len()
+                .checked_sub(back)
+                .map(|i| &snapshots[i])
+                .ok_or_else(|| "Not enough snapshots yet, they are taken on every sync that changes something".into()),
+        }
+    };
+    let (old, new) = (pick(&args.old, 2)?, pick(&args.new, 1)?);
+    let changes = diff_timetables(
+        &parse_timetable(&read_snapshot(old)?)?.data.courseActions,
+        &parse_timetable(&read_snapshot(new)?)?.data.courseActions,
+    );
+
+    println!(
+        "Changes from {} ({}) to {} ({}):",
+        old.taken_at.format("%-d.%-m.%Y %H:%M"),
+        old.name,
+        new.taken_at.format("%-d.%-m.%Y %H:%M"),
+        new.name
+    );
+    for change in &changes {
+        println!(
+            "  {:<13} {:<6} {:<3} {:<40} {}",
+            change.kind.label(),
+            change.abbr,
+            change.class_type,
+            change.name,
+            change.summary()
+        );
+    }
+    println!("{} change{}", changes.len(), if changes.len() == 1 { "" } else { "s" });
+    Ok(())
+}
+
+/// The configured free time bounds with the command line overrides applied.
+fn free_time_config(args: &FreeArgs) -> Result<FreeTimeConfig, Box<dyn std::error::Error>> {
+    let mut config = load_config()?.free_time;
+    // The arguments are checked by clap, a failed parse here cannot happen.
+    if let Some(time) = args.day_start.as_deref().and_then(parse_time) {
+        config.day_start = time;
+    }
+    if let Some(time) = args.day_end.as_deref().and_then(parse_time) {
+        config.day_end = time;
+    }
+    config.min_minutes = args.min.unwrap_or(config.min_minutes);
+    config.weekends |= args.weekends;
+    if config.day_start >= config.day_end {
+        return Err(format!(
+            "The day has to start before it ends, got {}-{}",
+            config.day_start.format("%H:%M"),
+            config.day_end.format("%H:%M")
+        )
+        .into());
+    }
+    Ok(config)
+}
+
+/// Asked for days that run backwards, e.g. `--to` before `--from`.
+fn check_days(from: NaiveDate, to: NaiveDate) -> Result<(), Box<dyn std::error::Error>> {
+    if to < from {
+        return Err(format!("--to {} is before the first day {}", to, from).into());
+    }
+    Ok(())
+}
+
+fn print_free_slots(slots: &[FreeSlot], format: FreeFormat) -> Result<(), Box<dyn std::error::Error>> {
+    match format {
+        FreeFormat::Json => {
+            let slots: Vec<_> = slots.iter().map(|slot| slot.to_json()).collect();
+            println!("{}", serde_json::to_string_pretty(&slots)?);
+        }
+        FreeFormat::Text => {
+            for slot in slots {
+                println!(
+                    "{} {:<11} {}-{}  {}",
+                    weekday_abbr(slot.start.date()),
+                    slot.start.format("%-d.%-m.%Y"),
+                    slot.start.format("%H:%M"),
+                    slot.end.format("%H:%M"),
+                    format_short_duration(slot.end - slot.start)
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn free(args: &FreeArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let timetable = load_timetable()?;
+    let ignored_ids = load_ignored_ids();
+    let config = free_time_config(args)?;
+    let now = Local::now().naive_local();
+
+    let from = args.from.unwrap_or(now.date());
+    let to = args.to.unwrap_or(from + Duration::days(6));
+    check_days(from, to)?;
+    let courses = sorted_courses(&timetable, (!args.show_ignored).then_some(&ignored_ids));
+    print_free_slots(&free_slots(&course_times(&courses), from, to, &config, now), args.format)
+}
+
+pub fn free_busy(args: &FreeBusyArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let now = Local::now().naive_local();
+    match &args.action {
+        FreeBusyAction::Export(args) => {
+            let timetable = load_timetable()?;
+            let ignored_ids = load_ignored_ids();
+            let courses = sorted_courses(&timetable, (!args.show_ignored).then_some(&ignored_ids));
+            let from = args.from.unwrap_or(now.date());
+            let to = args.to.unwrap_or(from + Duration::days(27));
+            let free_busy = FreeBusy::new(&course_times(&courses), from, to, args.name.clone());
+            let data = serde_json::to_string_pretty(&free_busy)?;
+            match &args.output {
+                Some(path) => fs::write(path, data)?,
+                None => println!("{}", data),
+            }
+        }
+        FreeBusyAction::Common(args) => {
+            let files = args.files.iter().map(|path| load_free_busy(path)).collect::<Result<Vec<_>, _>>()?;
+            let (first, last) = common_range(&files).ok_or("The free/busy files do not cover any common days")?;
+            let config = free_time_config(&args.free)?;
+
+            let mut busy: Vec<_> = files.iter().flat_map(FreeBusy::times).collect();
+            let mut people: Vec<String> = files.iter().map(FreeBusy::label).collect();
+            if !args.without_me {
+                let timetable = load_timetable()?;
+                let ignored_ids = load_ignored_ids();
+                busy.extend(course_times(&sorted_courses(&timetable, (!args.free.show_ignored).then_some(&ignored_ids))));
+                people.insert(0, "you".to_string());
+            }
+
+            // Outside the days of a file nobody knows whether its owner is free.
+            check_days(args.free.from.unwrap_or(now.date()), args.free.to.unwrap_or(NaiveDate::MAX))?;
+            let from = args.free.from.unwrap_or(now.date()).max(first);
+            let to = args.free.to.unwrap_or(from + Duration::days(6)).min(last);
+            if to < from {
+                return Err(format!(
+                    "The free/busy files cover {} - {}, none of the days asked for",
+                    first.format("%-d.%-m.%Y"),
+                    last.format("%-d.%-m.%Y")
+                )
+                .into());
+            }
+            if let FreeFormat::Text = args.free.format {
+                println!(
+                    "Common free time of {}, {} - {}:",
+                    people.join(", "),
+                    from.format("%-d.%-m.%Y"),
+                    to.format("%-d.%-m.%Y")
+                );
+            }
+            print_free_slots(&free_slots(&busy, from, to, &config, now), args.free.format)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn places(args: &PlacesArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let directory = Directory::load()?;
+    for (code, place) in directory.search(args.query.as_deref().unwrap_or("")) {
+        println!(
+            "{:<6} {}{}",
+            code,
+            place.name.as_deref().unwrap_or(""),
+            place.address.as_ref().map_or(String::new(), |address| format!(", {}", address))
+        );
+        if let Some(note) = &place.note {
+            println!("       {}", note);
+        }
+        for (room, entry) in &place.rooms {
+            println!("  {:<8} {}", room, entry.text());
+        }
+    }
+    Ok(())
+}
+
+/// Whether any classes overlap, `main` turns that into the exit status.
+pub fn conflicts(args: &ConflictsArgs) -> Result<bool, Box<dyn std::error::Error>> {
+    let timetable = load_timetable()?;
+    let ignored_ids = load_ignored_ids();
+    let now = Local::now().naive_local();
+    let until = now + Duration::days(args.days);
+
+    let courses = sorted_courses(&timetable, (!args.show_ignored).then_some(&ignored_ids));
+    let conflicts: Vec<_> = find_conflicts(&courses)
+        .into_iter()
+        .filter(|conflict| conflict.end >= now && conflict.start < until)
+        .collect();
+    for conflict in &conflicts {
+        println!(
+            "{} {} {}-{} ({} min)",
+            weekday_abbr(conflict.start.date()),
+            conflict.start.format("%-d.%-m.%Y"),
+            conflict.start.format("%H:%M"),
+            conflict.end.format("%H:%M"),
+            conflict.minutes()
+        );
+        for course in [conflict.first, conflict.second] {
+            println!("  {}", format_course_line(course, now));
+        }
+    }
+    if conflicts.is_empty() {
+        println!("No overlapping classes in the next {} days.", args.days);
+        return Ok(false);
+    }
+    println!("{} conflict{}", conflicts.len(), if conflicts.len() == 1 { "" } else { "s" });
+    Ok(true)
+}
+
+pub async fn remind(args: &RemindArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = load_config()?.reminders;
+    if !args.offsets.is_empty() {
+        config.offsets = args.offsets.clone();
+    }
+    if args.command.is_some() {
+        config.command = args.command.clone();
+    }
+    if config.command.is_none() {
+        return Err(format!(
+            "No reminder command, pass --command or set reminders.command in {}",
+            config_path().display()
+        )
+        .into());
+    }
+
+    let mut reminders = Reminders::new(config);
+    loop {
+        // Re-read every round so syncs and ignore changes are picked up. A read
+        // can fail while a sync rewrites the file, the next round tries again.
+        match load_timetable() {
+            Ok(timetable) => {
+                let ignored_ids = load_ignored_ids();
+                let courses = sorted_courses(&timetable, Some(&ignored_ids));
+                let now = Local::now().naive_local();
+                for message in reminders.tick(&courses, now) {
+                    println!("{} {}", now.format("%H:%M"), message);
+                }
+            }
+            Err(e) if !args.once => eprintln!("Cannot read the timetable, retrying: {}", e),
+            Err(e) => return Err(e),
+        }
+        if args.once {
+            return Ok(());
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(args.interval)).await;
+    }
+}
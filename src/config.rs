@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::NaiveTime;
+use serde::{Deserialize, Deserializer};
+
+use crate::events::parse_time;
+
+/// User settings from `~/.config/ujep_tui/config.json`. Every section is optional.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct Config {
+    pub reminders: ReminderConfig,
+    pub attendance: AttendanceConfig,
+    pub history: HistoryConfig,
+    #[serde(deserialize_with = "deserialize_free_time")]
+    pub free_time: FreeTimeConfig,
+    pub travel: TravelConfig,
+    pub theme: ThemeConfig,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct ReminderConfig {
+    /// Minutes before `timeFrom` at which to fire, e.g. `[10, 60]`.
+    pub offsets: Vec<i64>,
+    /// Shell command to run, class fields are passed as `UJEP_*` variables
+    /// and `{placeholder}`s. Reminders are off while this is unset.
+    pub command: Option<String>,
+}
+
+impl Default for ReminderConfig {
+    fn default() -> Self {
+        ReminderConfig {
+            offsets: vec![10],
+            command: None,
+        }
+    }
+}
+
+#[derive(Deserialize, Default, Clone)]
+#[serde(default)]
+pub struct AttendanceConfig {
+    /// Allowed absences per course, keyed `"KI/USU"` or `"KI/USU/Cv"` for one class type.
+    pub absence_limits: HashMap<String, u32>,
+}
+
+impl AttendanceConfig {
+    pub fn limit(&self, dept: &str, abbr: &str, class_type: &str) -> Option<u32> {
+        self.absence_limits
+            .get(&format!("{}/{}/{}", dept, abbr, class_type))
+            .or_else(|| self.absence_limits.get(&format!("{}/{}", dept, abbr)))
+            .copied()
+    }
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct HistoryConfig {
+    /// Snapshots kept in the cache, the oldest go first.
+    pub keep: usize,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        HistoryConfig { keep: 100 }
+    }
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct FreeTimeConfig {
+    /// Free time is only looked for between these hours, `"HH:MM"`.
+    #[serde(deserialize_with = "deserialize_time")]
+    pub day_start: NaiveTime,
+    #[serde(deserialize_with = "deserialize_time")]
+    pub day_end: NaiveTime,
+    /// Shorter gaps are not worth listing.
+    pub min_minutes: i64,
+    pub weekends: bool,
+}
+
+impl Default for FreeTimeConfig {
+    fn default() -> Self {
+        FreeTimeConfig {
+            day_start: NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+            day_end: NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
+            min_minutes: 30,
+            weekends: false,
+        }
+    }
+}
+
+fn deserialize_free_time<'de, D: Deserializer<'de>>(deserializer: D) -> Result<FreeTimeConfig, D::Error> {
+    let config = FreeTimeConfig::deserialize(deserializer)?;
+    if config.day_start >= config.day_end {
+        return Err(serde::de::Error::custom("free_time: day_start has to be before day_end"));
+    }
+    Ok(config)
+}
+
+fn deserialize_time<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveTime, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    parse_time(&value).ok_or_else(|| serde::de::Error::custom(format!("invalid time '{}', expected HH:MM", value)))
+}
+
+#[derive(Deserialize, Default, Clone)]
+#[serde(default)]
+pub struct TravelConfig {
+    /// Walking minutes between places, each pair in either order, e.g. `{"CP": {"MF": 15}}`.
+    pub minutes: HashMap<String, HashMap<String, i64>>,
+}
+
+impl TravelConfig {
+    /// Minutes to get from one place to another, `None` within a place or for unknown pairs.
+    pub fn minutes(&self, from: &str, to: &str) -> Option<i64> {
+        if from == to {
+            return None;
+        }
+        let lookup = |a: &str, b: &str| self.minutes.get(a).and_then(|times| times.get(b)).copied();
+        lookup(from, to).or_else(|| lookup(to, from))
+    }
+}
+
+#[derive(Deserialize, Default, Clone)]
+#[serde(default)]
+pub struct ThemeConfig {
+    /// A built-in or own theme. Unset means dark, or no colours when `NO_COLOR` is set.
+    pub name: Option<String>,
+    pub themes: HashMap<String, UserTheme>,
+}
+
+/// A built-in theme with some style slots replaced, e.g. `{"base": "light", "header": {"fg": "red"}}`.
+#[derive(Deserialize, Clone)]
+pub struct UserTheme {
+    #[serde(default = "default_base")]
+    pub base: String,
+    #[serde(flatten)]
+    pub slots: HashMap<String, SlotSpec>,
+}
+
+fn default_base() -> String {
+    "dark".to_string()
+}
+
+#[derive(Deserialize, Default, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct SlotSpec {
+    pub fg: Option<String>,
+    pub bg: Option<String>,
+    pub bold: bool,
+    pub italic: bool,
+    pub underlined: bool,
+    pub reversed: bool,
+    pub dim: bool,
+}
+
+pub fn config_dir() -> PathBuf {
+    let mut path = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
+    path.push("ujep_tui");
+    path
+}
+
+pub fn config_path() -> PathBuf {
+    config_dir().join("config.json")
+}
+
+/// A missing config file means defaults, a broken one is an error worth reporting.
+pub fn load_config() -> Result<Config, Box<dyn std::error::Error>> {
+    let path = config_path();
+    match fs::read_to_string(&path) {
+        Ok(data) => serde_json::from_str(&data)
+            .map_err(|e| format!("Invalid config {}: {}", path.display(), e).into()),
+        Err(_) => Ok(Config::default()),
+    }
+}
@@ -0,0 +1,98 @@
+use std::collections::HashSet;
+
+use chrono::NaiveDateTime;
+
+use crate::timetable::{parse_course_datetime, session_key, CourseAction};
+
+/// Two sessions that take place at the same time, `first` starts earlier.
+pub struct Conflict<'c> {
+    pub first: &'c CourseAction,
+    pub second: &'c CourseAction,
+    /// The time both sessions share.
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+}
+
+impl Conflict<'_> {
+    pub fn minutes(&self) -> i64 {
+        (self.end - self.start).num_minutes()
+    }
+}
+
+/// Overlapping pairs among `courses`, in order of the overlap start. A session
+/// listed twice, e.g. once per teacher, does not conflict with itself.
+pub fn find_conflicts<'c>(courses: &[&'c CourseAction]) -> Vec<Conflict<'c>> {
+    let mut timed: Vec<(NaiveDateTime, NaiveDateTime, &CourseAction)> = courses
+        .iter()
+        .filter_map(|&course| parse_course_datetime(course).map(|(start, end)| (start, end, course)))
+        .collect();
+    timed.sort_by_key(|&(start, end, _)| (start, end));
+
+    let mut conflicts = Vec::new();
+    for (i, &(start, end, first)) in timed.iter().enumerate() {
+        for &(other_start, other_end, second) in timed[i + 1..].iter().take_while(|(s, _, _)| *s < end) {
+            if session_key(first).is_some() && session_key(first) == session_key(second) {
+                continue;
+            }
+            conflicts.push(Conflict { first, second, start: other_start.max(start), end: other_end.min(end) });
+        }
+    }
+    conflicts.sort_by_key(|conflict| conflict.start);
+    conflicts
+}
+
+/// Session keys of all conflicting sessions, marked in the table.
+pub fn conflicting_sessions(conflicts: &[Conflict]) -> HashSet<String> {
+    conflicts
+        .iter()
+        .flat_map(|conflict| [conflict.first, conflict.second])
+        .filter_map(session_key)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timetable::test_course;
+
+    fn pairs(conflicts: &[Conflict]) -> Vec<(u32, u32, i64)> {
+        conflicts
+            .iter()
+            .map(|conflict| (conflict.first.id.unwrap(), conflict.second.id.unwrap(), conflict.minutes()))
+            .collect()
+    }
+
+    #[test]
+    fn overlapping_sessions_conflict_for_the_shared_time() {
+        let (a, b) = (test_course(1, "20.10.2026", "09:00", "11:00"), test_course(2, "20.10.2026", "10:00", "11:50"));
+        let conflicts = find_conflicts(&[&b, &a]);
+        assert_eq!(pairs(&conflicts), vec![(1, 2, 60)]);
+        assert_eq!(conflicts[0].start.format("%H:%M").to_string(), "10:00");
+        assert_eq!(conflicts[0].end.format("%H:%M").to_string(), "11:00");
+    }
+
+    #[test]
+    fn back_to_back_and_other_days_do_not_conflict() {
+        let a = test_course(1, "20.10.2026", "09:00", "10:50");
+        let b = test_course(2, "20.10.2026", "10:50", "12:00");
+        let c = test_course(3, "21.10.2026", "09:00", "10:50");
+        assert!(find_conflicts(&[&a, &b, &c]).is_empty());
+    }
+
+    #[test]
+    fn same_session_listed_twice_is_no_conflict() {
+        let a = test_course(1, "20.10.2026", "09:00", "10:50");
+        let mut twice = a.clone();
+        twice.contact = Some("second teacher".to_string());
+        assert!(find_conflicts(&[&a, &twice]).is_empty());
+    }
+
+    #[test]
+    fn long_session_conflicts_with_each_one_inside() {
+        let long = test_course(1, "21.10.2026", "08:00", "12:00");
+        let (a, b) = (test_course(2, "21.10.2026", "08:30", "09:00"), test_course(3, "21.10.2026", "10:00", "11:00"));
+        let conflicts = find_conflicts(&[&a, &b, &long]);
+        assert_eq!(pairs(&conflicts), vec![(1, 2, 30), (1, 3, 60)]);
+        assert_eq!(conflicting_sessions(&conflicts).len(), 3);
+    }
+}
@@ -0,0 +1,96 @@
+use chrono::NaiveDateTime;
+use crossterm::event::KeyCode;
+use ratatui::{
+    backend::Backend,
+    layout::{Constraint, Rect},
+    widgets::{Block, Borders, Clear, Row, Table, TableState},
+};
+
+use crate::app::{App, View};
+use crate::conflicts::Conflict;
+use crate::courses_view::highlighted;
+use crate::theme::theme;
+use crate::timetable::{weekday_abbr, CourseAction};
+
+/// Conflicts that have not ended yet, in date order.
+pub fn upcoming_conflicts<'a, 'c>(conflicts: &'a [Conflict<'c>], now: NaiveDateTime) -> Vec<&'a Conflict<'c>> {
+    conflicts.iter().filter(|conflict| conflict.end >= now).collect()
+}
+
+fn session(course: &CourseAction) -> String {
+    format!(
+        "{}-{} {} {} ({} {})",
+        course.timeFrom.as_deref().unwrap_or(""),
+        course.timeTo.as_deref().unwrap_or(""),
+        course.abbr.as_deref().unwrap_or(""),
+        course.class_type.as_deref().unwrap_or(""),
+        course.place.as_deref().unwrap_or(""),
+        course.room.as_deref().unwrap_or("")
+    )
+}
+
+pub fn draw_conflicts_view<B: Backend>(f: &mut ratatui::Frame<B>, area: Rect, app: &App, now: NaiveDateTime) {
+    let conflicts = upcoming_conflicts(&app.conflicts, now);
+    let header_style = theme().header;
+
+    let rows: Vec<Row> = conflicts
+        .iter()
+        .map(|conflict| {
+            Row::new(vec![
+                format!("{} {}", weekday_abbr(conflict.start.date()), conflict.start.format("%-d.%-m.%Y")),
+                format!("{} - {}", conflict.start.format("%H:%M"), conflict.end.format("%H:%M")),
+                format!("{} min", conflict.minutes()),
+                session(conflict.first),
+                session(conflict.second),
+            ])
+        })
+        .collect();
+
+    let title = if conflicts.is_empty() {
+        "Overlapping classes - none ahead".to_string()
+    } else {
+        format!("Overlapping classes ({}, [Enter] details, [i]gnore one to resolve)", conflicts.len())
+    };
+    let table = Table::new(rows)
+        .header(Row::new(vec!["Date", "Overlap", "Length", "First", "Second"]).style(header_style))
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .widths(&[
+            Constraint::Length(14),
+            Constraint::Length(14),
+            Constraint::Length(7),
+            Constraint::Percentage(35),
+            Constraint::Percentage(35),
+        ]);
+
+    let mut state = TableState::default();
+    state.select(app.view_selected);
+    f.render_widget(Clear, area);
+    f.render_stateful_widget(highlighted(table), area, &mut state);
+}
+
+/// Keys of the conflicts view. Returns false for keys left to the main key handling.
+pub fn handle_conflicts_key(app: &mut App, code: KeyCode, now: NaiveDateTime) -> bool {
+    let count = upcoming_conflicts(&app.conflicts, now).len();
+    match code {
+        KeyCode::Down | KeyCode::Char('j') if count > 0 => {
+            app.view_selected = Some(app.view_selected.map_or(0, |i| (i + 1).min(count - 1)));
+        }
+        KeyCode::Up | KeyCode::Char('k') if count > 0 => {
+            app.view_selected = Some(app.view_selected.map_or(0, |i| i.saturating_sub(1)));
+        }
+        KeyCode::Enter | KeyCode::Char('l') => {
+            let first = app
+                .view_selected
+                .and_then(|i| upcoming_conflicts(&app.conflicts, now).get(i).map(|conflict| conflict.first));
+            if let Some(course) = first {
+                app.details_course = Some(course);
+                app.show_details = true;
+            }
+        }
+        KeyCode::Char('o') | KeyCode::Backspace | KeyCode::Char('h') => {
+            app.view = View::Table;
+        }
+        _ => return false,
+    }
+    true
+}
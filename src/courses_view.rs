@@ -0,0 +1,340 @@
+use std::collections::BTreeSet;
+
+use chrono::{Datelike, NaiveDateTime};
+use crossterm::event::KeyCode;
+use ratatui::{
+    backend::Backend,
+    layout::{Constraint, Rect},
+    style::Style,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Clear, Row, Table, TableState},
+};
+
+use crate::app::{App, View};
+use crate::attendance::{save_attendance, Attendance, AttendanceStats};
+use crate::events::is_user_event;
+use crate::exams::is_exam;
+use crate::export::normalize_week_type;
+use crate::theme::theme;
+use crate::timetable::{is_course_ongoing, parse_course_datetime, session_key, weekday_abbr, CourseAction};
+use crate::week_view::handle_selection_key;
+
+/// All sessions of one course of one type, e.g. the KI/USU exercises.
+pub struct CourseGroup<'a> {
+    pub dept: String,
+    pub abbr: String,
+    pub class_type: String,
+    pub name: String,
+    /// Sorted by start, undated sessions last.
+    pub sessions: Vec<&'a CourseAction>,
+}
+
+impl<'a> CourseGroup<'a> {
+    fn times(&self) -> impl Iterator<Item = (NaiveDateTime, NaiveDateTime)> + '_ {
+        self.sessions.iter().filter_map(|c| parse_course_datetime(c))
+    }
+
+    /// Weekday and time slots, e.g. "Po 11:00-12:50, St 14:00-14:50 every".
+    pub fn pattern(&self) -> String {
+        let slots: BTreeSet<(u32, String)> = self
+            .times()
+            .map(|(start, end)| {
+                let weekday = start.date().weekday().num_days_from_monday();
+                let slot = format!(
+                    "{} {}-{}",
+                    weekday_abbr(start.date()),
+                    start.format("%H:%M"),
+                    end.format("%H:%M")
+                );
+                (weekday, slot)
+            })
+            .collect();
+        let mut pattern = slots.into_iter().map(|(_, slot)| slot).collect::<Vec<_>>().join(", ");
+        if let Some(week_type) = self.sessions.iter().find_map(|c| c.weekType.as_deref()) {
+            pattern.push(' ');
+            pattern.push_str(&normalize_week_type(week_type));
+        }
+        pattern
+    }
+
+    pub fn weeks(&self) -> String {
+        let from = self.sessions.iter().filter_map(|c| c.weekFrom).min();
+        let to = self.sessions.iter().filter_map(|c| c.weekTo).max();
+        match (from, to) {
+            (Some(from), Some(to)) => format!("{}-{}", from, to),
+            _ => "N/A".to_string(),
+        }
+    }
+
+    pub fn places(&self) -> String {
+        let places: BTreeSet<String> = self
+            .sessions
+            .iter()
+            .map(|c| format!("{} {}", c.place.as_deref().unwrap_or("N/A"), c.room.as_deref().unwrap_or("N/A")))
+            .collect();
+        places.into_iter().collect::<Vec<_>>().join(", ")
+    }
+
+    /// Sessions that already ended.
+    pub fn held(&self, now: NaiveDateTime) -> usize {
+        self.times().filter(|(_, end)| *end <= now).count()
+    }
+
+    pub fn contact_hours(&self) -> f64 {
+        self.times().map(|(start, end)| (end - start).num_minutes() as f64 / 60.0).sum()
+    }
+
+    pub fn next(&self, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        self.times().map(|(start, _)| start).find(|start| *start > now)
+    }
+}
+
+/// Groups course actions by dept, abbreviation and type, sorted by abbreviation.
+pub fn group_courses<'a>(courses: &[&'a CourseAction]) -> Vec<CourseGroup<'a>> {
+    let mut groups: Vec<CourseGroup> = Vec::new();
+    // Own events and exams are not courses.
+    for &course in courses.iter().filter(|c| !is_user_event(c) && !is_exam(c)) {
+        let dept = course.dept.clone().unwrap_or_default();
+        let abbr = course.abbr.clone().unwrap_or_default();
+        let class_type = course.class_type.clone().unwrap_or_default();
+        match groups
+            .iter_mut()
+            .find(|g| g.dept == dept && g.abbr == abbr && g.class_type == class_type)
+        {
+            Some(group) => group.sessions.push(course),
+            None => groups.push(CourseGroup {
+                dept,
+                abbr,
+                class_type,
+                name: course.name.clone().unwrap_or_default(),
+                sessions: vec![course],
+            }),
+        }
+    }
+    for group in &mut groups {
+        group.sessions.sort_by_key(|c| parse_course_datetime(c).map_or(NaiveDateTime::MAX, |(start, _)| start));
+    }
+    groups.sort_by(|a, b| (&a.abbr, &a.dept, &a.class_type).cmp(&(&b.abbr, &b.dept, &b.class_type)));
+    groups
+}
+
+fn group_attendance(app: &App, group: &CourseGroup, now: NaiveDateTime) -> AttendanceStats {
+    let limit = app.attendance_config.limit(&group.dept, &group.abbr, &group.class_type);
+    AttendanceStats::compute(&group.sessions, &app.attendance, limit, now)
+}
+
+fn warning_style(stats: &AttendanceStats) -> Style {
+    match stats.absences_left() {
+        Some(left) if left < 0 => theme().bad,
+        Some(left) if left <= 1 => theme().warning,
+        _ => Style::default(),
+    }
+}
+
+pub fn highlighted(table: Table) -> Table {
+    table
+        .highlight_style(theme().selected)
+        .highlight_symbol("> ")
+}
+
+pub fn draw_courses_view<B: Backend>(
+    f: &mut ratatui::Frame<B>,
+    area: Rect,
+    app: &App,
+    displayed: &[&CourseAction],
+    now: NaiveDateTime,
+) {
+    let groups = group_courses(displayed);
+    let header_style = theme().header;
+    let mut state = TableState::default();
+    state.select(app.view_selected);
+    f.render_widget(Clear, area);
+
+    if let Some(group) = app.courses_group.and_then(|i| groups.get(i)) {
+        let next = group.next(now);
+        let rows: Vec<Row> = group
+            .sessions
+            .iter()
+            .map(|course| {
+                let times = parse_course_datetime(course);
+                let state = match times {
+                    _ if is_course_ongoing(course, now) => "ONGOING",
+                    Some((start, _)) if Some(start) == next => "NEXT",
+                    Some((_, end)) if end <= now => "held",
+                    _ => "",
+                };
+                let date = times.map_or("N/A".to_string(), |(start, _)| {
+                    format!("{} {}", weekday_abbr(start.date()), start.format("%-d.%-m.%Y"))
+                });
+                let attendance = match session_key(course).and_then(|key| app.attendance.get(&key)) {
+                    Some(attendance) => attendance.label(),
+                    None if times.is_some_and(|(start, _)| start <= now) => "?",
+                    None => "",
+                };
+                Row::new(vec![
+                    state.to_string(),
+                    date,
+                    format!(
+                        "{} - {}",
+                        course.timeFrom.as_deref().unwrap_or("N/A"),
+                        course.timeTo.as_deref().unwrap_or("N/A")
+                    ),
+                    course.place.clone().unwrap_or_default(),
+                    course.room.clone().unwrap_or_default(),
+                    attendance.to_string(),
+                    course.note.clone().unwrap_or_default(),
+                ])
+            })
+            .collect();
+        let stats = group_attendance(app, group, now);
+        let mut summary = format!(
+            "{} attended, {} missed, {} excused",
+            stats.attended, stats.missed, stats.excused
+        );
+        if let Some(left) = stats.absences_left() {
+            summary.push_str(&format!(", {}/{} absences left", left.max(0), stats.limit.unwrap_or(0)));
+        }
+        if let Some(warning) = stats.warning() {
+            summary.push_str(&format!(" - {}", warning.to_uppercase()));
+        }
+        let title = Spans::from(vec![
+            Span::raw(format!("{} {} {} - {}: ", group.dept, group.abbr, group.class_type, group.name)),
+            Span::styled(summary, warning_style(&stats)),
+            Span::raw(" ([a/m/e] mark, [h] back)"),
+        ]);
+        let table = Table::new(rows)
+            .header(Row::new(vec!["", "Date", "Time", "Place", "Room", "Attendance", "Note"]).style(header_style))
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .widths(&[
+                Constraint::Length(8),
+                Constraint::Length(14),
+                Constraint::Length(14),
+                Constraint::Length(6),
+                Constraint::Length(8),
+                Constraint::Length(10),
+                Constraint::Percentage(40),
+            ]);
+        f.render_stateful_widget(highlighted(table), area, &mut state);
+        return;
+    }
+
+    let mut warnings = 0;
+    let rows: Vec<Row> = groups
+        .iter()
+        .map(|group| {
+            let held = group.held(now);
+            let stats = group_attendance(app, group, now);
+            let absences = match stats.limit {
+                Some(limit) => format!("{}/{}", stats.missed, limit),
+                None => stats.missed.to_string(),
+            };
+            if stats.warning().is_some() {
+                warnings += 1;
+            }
+            let next = group.next(now).map_or("-".to_string(), |start| {
+                format!("{} {}", weekday_abbr(start.date()), start.format("%-d.%-m. %H:%M"))
+            });
+            Row::new(vec![
+                group.abbr.clone(),
+                group.class_type.clone(),
+                group.name.clone(),
+                group.pattern(),
+                group.weeks(),
+                group.places(),
+                format!("{}/{}", held, group.sessions.len() - held),
+                format!("{:.1}", group.contact_hours()),
+                absences,
+                next,
+            ])
+            .style(warning_style(&stats))
+        })
+        .collect();
+    let mut title = format!("Courses ({}, [Enter] sessions)", groups.len());
+    if warnings > 0 {
+        title.push_str(&format!(" - {} near or over the absence limit", warnings));
+    }
+    let table = Table::new(rows)
+        .header(
+            Row::new(vec![
+                "Course", "Type", "Name", "Pattern", "Weeks", "Place", "Held/Left", "Hours", "Absent", "Next",
+            ])
+            .style(header_style),
+        )
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .widths(&[
+            Constraint::Length(6),
+            Constraint::Length(4),
+            Constraint::Percentage(19),
+            Constraint::Percentage(19),
+            Constraint::Length(5),
+            Constraint::Length(10),
+            Constraint::Length(9),
+            Constraint::Length(5),
+            Constraint::Length(6),
+            Constraint::Length(16),
+        ]);
+    f.render_stateful_widget(highlighted(table), area, &mut state);
+}
+
+/// Keys of the courses view. Returns false for keys left to the main key handling.
+pub fn handle_courses_key<'a>(
+    app: &mut App<'a>,
+    displayed: &[&'a CourseAction],
+    code: KeyCode,
+    now: NaiveDateTime,
+) -> bool {
+    let groups = group_courses(displayed);
+
+    if let Some(group) = app.courses_group.and_then(|i| groups.get(i)) {
+        if handle_selection_key(app, &group.sessions, code) {
+            return true;
+        }
+        let mark = match code {
+            KeyCode::Char('a') => Attendance::Attended,
+            KeyCode::Char('m') => Attendance::Missed,
+            KeyCode::Char('e') => Attendance::Excused,
+            KeyCode::Backspace | KeyCode::Char('h') => {
+                app.view_selected = app.courses_group.take();
+                return true;
+            }
+            _ => return false,
+        };
+        // Only sessions that already started, and never someone else's timetable.
+        let session = app.view_selected.and_then(|i| group.sessions.get(i));
+        let started = session
+            .and_then(|course| parse_course_datetime(course))
+            .is_some_and(|(start, _)| start <= now);
+        if let Some(key) = session.and_then(|course| session_key(course)).filter(|_| started && !app.read_only) {
+            // Marking the same again clears it.
+            if app.attendance.get(&key) == Some(&mark) {
+                app.attendance.remove(&key);
+            } else {
+                app.attendance.insert(key, mark);
+            }
+            save_attendance(&app.attendance);
+        }
+        return true;
+    }
+
+    match code {
+        KeyCode::Down | KeyCode::Char('j') if !groups.is_empty() => {
+            app.view_selected = Some(app.view_selected.map_or(0, |i| (i + 1).min(groups.len() - 1)));
+        }
+        KeyCode::Up | KeyCode::Char('k') if !groups.is_empty() => {
+            app.view_selected = Some(app.view_selected.map_or(0, |i| i.saturating_sub(1)));
+        }
+        KeyCode::Enter | KeyCode::Char('l') => {
+            if let Some(index) = app.view_selected.filter(|&i| i < groups.len()) {
+                // Start on the next session, the held ones are above it.
+                let group = &groups[index];
+                app.courses_group = Some(index);
+                app.view_selected = Some(group.held(now).min(group.sessions.len() - 1));
+            }
+        }
+        KeyCode::Char('c') | KeyCode::Backspace | KeyCode::Char('h') => {
+            app.view = View::Table;
+        }
+        _ => return false,
+    }
+    true
+}
@@ -0,0 +1,116 @@
+use std::fs;
+use std::path::Path;
+
+use chrono::{Duration, Local, NaiveDateTime};
+use tokio::io::AsyncWriteExt;
+use tokio::net::UnixListener;
+use tokio::sync::watch;
+
+use crate::cache::{cache_path, load_ignored_ids, load_timetable, retrieved_at};
+use crate::cli::DaemonArgs;
+use crate::commands::sorted_courses;
+use crate::fetch_timetable::fetch_timetable;
+use crate::status::snapshot;
+use crate::timetable::Timetable;
+
+/// Writes via a temporary file so readers never see half a state.
+fn write_state_file(path: &Path, state: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, state)?;
+    fs::rename(tmp, path)
+}
+
+/// Every client gets the current state as one JSON line on connect and another
+/// line on each change, until it hangs up.
+fn serve_socket(path: &Path, states: watch::Receiver<String>) -> Result<(), Box<dyn std::error::Error>> {
+    if path.exists() {
+        // Only a socket left behind by a crashed daemon may go, not a live one.
+        if std::os::unix::net::UnixStream::connect(path).is_ok() {
+            return Err(format!("A daemon is already running on {}", path.display()).into());
+        }
+        fs::remove_file(path)?;
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let listener = UnixListener::bind(path)?;
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut states = states.clone();
+            tokio::spawn(async move {
+                loop {
+                    let state = states.borrow_and_update().clone();
+                    // Nothing computed yet right after start.
+                    if !state.is_empty() && stream.write_all(format!("{}\n", state).as_bytes()).await.is_err() {
+                        break;
+                    }
+                    if states.changed().await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    });
+    Ok(())
+}
+
+pub async fn run_daemon(args: &DaemonArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let state_file = args.state_file.clone().unwrap_or_else(|| cache_path("state.json"));
+    let socket = args.socket.clone().unwrap_or_else(|| {
+        dirs::runtime_dir()
+            .map(|dir| dir.join("ujep_tui.sock"))
+            .unwrap_or_else(|| cache_path("ujep_tui.sock"))
+    });
+
+    let (sender, receiver) = watch::channel(String::new());
+    if !args.no_socket {
+        serve_socket(&socket, receiver)?;
+        eprintln!("Publishing on {}", socket.display());
+    }
+    eprintln!("Writing {}", state_file.display());
+
+    let mut timetable: Timetable = load_timetable()?;
+    let mut last_sync: Option<NaiveDateTime> = None;
+    let mut last_state = serde_json::Value::Null;
+
+    loop {
+        let now = Local::now().naive_local();
+
+        if args.sync_interval > 0
+            && last_sync.is_none_or(|last| now - last >= Duration::minutes(args.sync_interval as i64))
+        {
+            last_sync = Some(now);
+            // Keep serving the cache when offline or logged out.
+            if let Err(e) = fetch_timetable().await {
+                eprintln!("Sync failed: {}", e);
+            }
+        }
+        // Cheap enough to re-read every round, picks up syncs made by the TUI too.
+        if let Ok(fresh) = load_timetable() {
+            timetable = fresh;
+        }
+
+        let ignored_ids = load_ignored_ids();
+        let courses = sorted_courses(&timetable, Some(&ignored_ids));
+        let state = snapshot(&courses, now);
+
+        if state != last_state {
+            let mut published = state.clone();
+            published["updated_at"] = now.format("%Y-%m-%dT%H:%M:%S").to_string().into();
+            published["retrieved_at"] = retrieved_at(&timetable)
+                .map(|dt| dt.format("%Y-%m-%dT%H:%M:%S").to_string())
+                .into();
+            let line = published.to_string();
+            if let Err(e) = write_state_file(&state_file, &line) {
+                eprintln!("Cannot write {}: {}", state_file.display(), e);
+            }
+            sender.send_replace(line);
+            last_state = state;
+        }
+
+        tokio::time::sleep(std::time::Duration::from_secs(args.interval)).await;
+    }
+}
@@ -0,0 +1,218 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
+use crossterm::event::KeyCode;
+use ratatui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::Modifier,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Clear, Gauge, Paragraph},
+};
+
+use crate::app::{App, View};
+use crate::status::{format_short_duration, ClassStatus};
+use crate::theme::theme;
+use crate::timetable::{is_course_ongoing, parse_course_datetime, weekday_abbr, CourseAction};
+use crate::travel::warning_for;
+use crate::week_view::{assign_lanes, block_style, handle_selection_key, hour_range, TimeScale};
+
+/// Courses starting on `date`, sorted by start.
+pub fn day_courses<'a>(displayed: &[&'a CourseAction], date: NaiveDate) -> Vec<&'a CourseAction> {
+    let mut courses: Vec<&CourseAction> = displayed
+        .iter()
+        .filter(|c| parse_course_datetime(c).is_some_and(|(start, _)| start.date() == date))
+        .copied()
+        .collect();
+    courses.sort_by_key(|c| parse_course_datetime(c).map(|(start, _)| start));
+    courses
+}
+
+/// Free stretches between classes sorted by start, overlapping classes count as one.
+pub fn gaps(times: &[(NaiveDateTime, NaiveDateTime)]) -> Vec<(NaiveDateTime, NaiveDateTime)> {
+    let mut gaps = Vec::new();
+    let mut busy_until: Option<NaiveDateTime> = None;
+    for &(start, end) in times {
+        if let Some(until) = busy_until {
+            if start > until {
+                gaps.push((until, start));
+            }
+        }
+        busy_until = Some(busy_until.map_or(end, |until| until.max(end)));
+    }
+    gaps
+}
+
+pub fn draw_day_view<B: Backend>(
+    f: &mut ratatui::Frame<B>,
+    area: Rect,
+    app: &App,
+    displayed: &[&CourseAction],
+    now: NaiveDateTime,
+) {
+    let date = app.view_date;
+    let courses = day_courses(displayed, date);
+    let is_today = date == now.date();
+
+    let title = format!(
+        "{} {}{} ([</>] day, [w]eek)",
+        weekday_abbr(date),
+        date.format("%-d.%-m.%Y"),
+        if is_today { " - today" } else { "" }
+    );
+    f.render_widget(Clear, area);
+    f.render_widget(Block::default().borders(Borders::ALL).title(title), area);
+
+    let inner = Rect {
+        x: area.x + 1,
+        y: area.y + 1,
+        width: area.width.saturating_sub(2),
+        height: area.height.saturating_sub(2),
+    };
+    if inner.height < 3 || inner.width < 20 {
+        return;
+    }
+
+    let axis_width = 6;
+    let width = inner.width - axis_width;
+    let x = inner.x + axis_width;
+    let scale = TimeScale::new(inner.y, inner.height, hour_range(&courses));
+    let bottom_row = inner.y + inner.height;
+
+    for hour in scale.first_hour..scale.last_hour {
+        let y = scale.row(NaiveTime::from_hms_opt(hour, 0, 0).unwrap());
+        let label = Paragraph::new(format!("{:02}:00", hour)).style(theme().muted);
+        f.render_widget(label, Rect { x: inner.x, y, width: axis_width, height: 1 });
+        let mark = Paragraph::new("┄".repeat(width as usize)).style(theme().muted);
+        f.render_widget(mark, Rect { x, y, width, height: 1 });
+    }
+
+    // Same numbers as the status bar, but only for today's classes.
+    let status = if is_today {
+        ClassStatus::compute(&courses, now)
+    } else {
+        ClassStatus::None
+    };
+    let gauge_style = theme().gauge;
+
+    let times: Vec<(NaiveDateTime, NaiveDateTime)> =
+        courses.iter().filter_map(|c| parse_course_datetime(c)).collect();
+
+    for (start, end) in gaps(&times) {
+        let (top, bottom) = (scale.row(start.time()), scale.row(end.time()));
+        if bottom <= top + 1 {
+            continue;
+        }
+        let rect = Rect { x, y: (top + bottom) / 2, width, height: 1 };
+        let label = format!("{} free", format_short_duration(end - start));
+        let tight = courses
+            .iter()
+            .filter(|c| parse_course_datetime(c).is_some_and(|(next_start, _)| next_start == end))
+            .find_map(|c| warning_for(&app.travel_warnings, c));
+        if let Some(warning) = tight {
+            let paragraph = Paragraph::new(format!("⚠ {}", warning.text()))
+                .alignment(Alignment::Center)
+                .style(theme().travel);
+            f.render_widget(paragraph, rect);
+            continue;
+        }
+        match status {
+            ClassStatus::Next { start: next_start, progress, .. } if next_start == end && now >= start => {
+                let gauge = Gauge::default().gauge_style(gauge_style).ratio(progress).label(label);
+                f.render_widget(gauge, rect);
+            }
+            _ => {
+                let paragraph = Paragraph::new(label)
+                    .alignment(Alignment::Center)
+                    .style(theme().muted.add_modifier(Modifier::ITALIC));
+                f.render_widget(paragraph, rect);
+            }
+        }
+    }
+
+    if is_today && now.time() >= NaiveTime::from_hms_opt(scale.first_hour, 0, 0).unwrap() {
+        let y = scale.row(now.time());
+        if y < bottom_row {
+            let marker_style = theme().now;
+            let label = Paragraph::new(now.format("%H:%M").to_string()).style(marker_style);
+            f.render_widget(label, Rect { x: inner.x, y, width: axis_width, height: 1 });
+            let line = Paragraph::new("─".repeat(width as usize)).style(marker_style);
+            f.render_widget(line, Rect { x, y, width, height: 1 });
+        }
+    }
+
+    let (lanes, lane_count) = assign_lanes(&times);
+    let lane_width = (width / lane_count as u16).max(1);
+    for ((i, course), ((start, end), lane)) in courses.iter().enumerate().zip(times.iter().zip(lanes)) {
+        let top = scale.row(start.time());
+        if top >= bottom_row {
+            continue;
+        }
+        let bottom = scale.row(end.time()).max(top + 1).min(bottom_row);
+        let rect = Rect {
+            x: x + lane as u16 * lane_width,
+            y: top,
+            width: lane_width.saturating_sub(1).max(1),
+            height: bottom - top,
+        };
+        let text = format!(
+            "{} - {}  {} {}  {}\n{} {}",
+            course.timeFrom.as_deref().unwrap_or(""),
+            course.timeTo.as_deref().unwrap_or(""),
+            course.abbr.as_deref().unwrap_or("N/A"),
+            course.class_type.as_deref().unwrap_or(""),
+            course.name.as_deref().unwrap_or(""),
+            course.place.as_deref().unwrap_or(""),
+            course.room.as_deref().unwrap_or(""),
+        );
+        let ongoing = is_course_ongoing(course, now);
+        let style = block_style(app.view_selected == Some(i), ongoing, course);
+        f.render_widget(Clear, rect);
+        f.render_widget(Paragraph::new(text).style(style), rect);
+        // Short breaks leave no room between the blocks, the block itself carries the warning.
+        if let Some(warning) = warning_for(&app.travel_warnings, course) {
+            let marker = Paragraph::new(Spans::from(Span::styled(
+                format!("⚠ {} ", warning.text()),
+                style.patch(theme().travel),
+            )))
+            .alignment(Alignment::Right);
+            let y = if rect.height > 1 { rect.y + 1 } else { rect.y };
+            f.render_widget(marker, Rect { y, height: 1, ..rect });
+        }
+
+        if let ClassStatus::Ongoing { progress, .. } = status {
+            if ongoing && rect.height > 2 {
+                let gauge = Gauge::default()
+                    .gauge_style(gauge_style)
+                    .ratio(progress)
+                    .label(format!("{:.1}%, {} left", progress * 100.0, format_short_duration(*end - now)));
+                f.render_widget(gauge, Rect { y: rect.y + rect.height - 1, height: 1, ..rect });
+            }
+        }
+    }
+}
+
+/// Keys of the day view. Returns false for keys left to the main key handling.
+pub fn handle_day_key<'a>(app: &mut App<'a>, displayed: &[&'a CourseAction], code: KeyCode) -> bool {
+    let courses = day_courses(displayed, app.view_date);
+    if handle_selection_key(app, &courses, code) {
+        return true;
+    }
+    match code {
+        KeyCode::Left | KeyCode::Char('<') | KeyCode::PageUp => {
+            app.view_date -= Duration::days(1);
+            app.view_selected = None;
+        }
+        KeyCode::Right | KeyCode::Char('>') | KeyCode::PageDown => {
+            app.view_date += Duration::days(1);
+            app.view_selected = None;
+        }
+        KeyCode::Char('w') => {
+            app.view = View::Week;
+            app.view_selected = None;
+        }
+        KeyCode::Char('d') | KeyCode::Backspace | KeyCode::Char('h') => {
+            app.view = View::Table;
+        }
+        _ => return false,
+    }
+    true
+}
@@ -0,0 +1,194 @@
+use std::fs;
+
+use chrono::{Duration, NaiveDate, NaiveTime};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::config::config_dir;
+use crate::exams::EXAM_ID_BASE;
+use crate::timetable::{weekday_abbr, CourseAction};
+
+/// Events get ids far above the action ids of the API, so they never collide.
+pub const EVENT_ID_BASE: u32 = 1_000_000_000;
+
+/// Own ids from here on would run into the exam ids.
+pub const MAX_EVENT_ID: u32 = EXAM_ID_BASE - EVENT_ID_BASE;
+
+/// How long a repeating event without `until` keeps repeating, about a semester.
+const DEFAULT_REPEAT_WEEKS: i64 = 26;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Repeat {
+    Daily,
+    Weekly,
+    Biweekly,
+}
+
+impl Repeat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Repeat::Daily => "daily",
+            Repeat::Weekly => "weekly",
+            Repeat::Biweekly => "biweekly",
+        }
+    }
+}
+
+/// An event of the user's own, e.g. a study group or a job shift.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct UserEvent {
+    pub id: u32,
+    pub title: String,
+    /// Shown in the type column, e.g. "exam" or "job".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    pub date: NaiveDate,
+    /// `HH:MM`
+    pub from: String,
+    /// `HH:MM`
+    pub to: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub place: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub room: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repeat: Option<Repeat>,
+    /// Last day of a repeating event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub until: Option<NaiveDate>,
+}
+
+pub fn parse_time(time: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(time, "%H:%M").ok()
+}
+
+impl UserEvent {
+    /// Dates the event takes place on.
+    pub fn dates(&self) -> Vec<NaiveDate> {
+        let step = match self.repeat {
+            None => return vec![self.date],
+            Some(Repeat::Daily) => Duration::days(1),
+            Some(Repeat::Weekly) => Duration::weeks(1),
+            Some(Repeat::Biweekly) => Duration::weeks(2),
+        };
+        let until = self.until.unwrap_or(self.date + Duration::weeks(DEFAULT_REPEAT_WEEKS));
+        let mut dates = Vec::new();
+        let mut date = self.date;
+        while date <= until {
+            dates.push(date);
+            date += step;
+        }
+        dates
+    }
+
+    /// One course action per occurrence, in the shape the API uses. A broken
+    /// hand-edited entry yields none.
+    pub fn course_actions(&self) -> Vec<CourseAction> {
+        if parse_time(&self.from).is_none() || parse_time(&self.to).is_none() {
+            return Vec::new();
+        }
+        self.dates()
+            .into_iter()
+            .map(|date| CourseAction {
+                id: Some(EVENT_ID_BASE + self.id),
+                name: Some(self.title.clone()),
+                dept: None,
+                abbr: Some(self.title.clone()),
+                year: None,
+                semester: None,
+                date: Some(date.format("%-d.%-m.%Y").to_string()),
+                timeFrom: Some(self.from.clone()),
+                timeTo: Some(self.to.clone()),
+                place: self.place.clone(),
+                room: self.room.clone(),
+                class_type: Some(self.kind.clone().unwrap_or_else(|| "Event".to_string())),
+                day: Some(weekday_abbr(date).to_string()),
+                weekType: None,
+                weekFrom: None,
+                weekTo: None,
+                note: self.note.clone(),
+                contact: None,
+                statut: None,
+                teachingTeacherStagId: None,
+            })
+            .collect()
+    }
+}
+
+/// Own events live next to the config, away from the synced cache.
+pub fn events_path() -> std::path::PathBuf {
+    config_dir().join("events.json")
+}
+
+/// The events of a file and why any hand-edited entries were left out: entries
+/// that do not parse and ids outside the event range are skipped one by one.
+/// Only data that is not a list at all is an error.
+pub fn parse_events(data: &str) -> Result<(Vec<UserEvent>, Vec<String>), serde_json::Error> {
+    let entries: Vec<serde_json::Value> = serde_json::from_str(data)?;
+    let mut events = Vec::new();
+    let mut skipped = Vec::new();
+    for (index, entry) in entries.into_iter().enumerate() {
+        match serde_json::from_value::<UserEvent>(entry) {
+            Ok(event) if event.id < MAX_EVENT_ID => events.push(event),
+            Ok(event) => skipped.push(format!("entry {}: id {} is not below {}", index + 1, event.id, MAX_EVENT_ID)),
+            Err(e) => skipped.push(format!("entry {}: {}", index + 1, e)),
+        }
+    }
+    Ok((events, skipped))
+}
+
+/// No file means no events yet.
+pub fn load_events() -> Result<(Vec<UserEvent>, Vec<String>), Box<dyn std::error::Error>> {
+    let path = events_path();
+    let data = match fs::read_to_string(&path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok((Vec::new(), Vec::new())),
+        Err(e) => return Err(format!("Cannot read {}: {}", path.display(), e).into()),
+    };
+    let (events, skipped) = parse_events(&data).map_err(|e| format!("Invalid events file {}: {}", path.display(), e))?;
+    let skipped = skipped.into_iter().map(|reason| format!("{} {}", path.display(), reason)).collect();
+    Ok((events, skipped))
+}
+
+pub fn save_events(events: &[UserEvent]) -> Result<(), Box<dyn std::error::Error>> {
+    fs::create_dir_all(config_dir())?;
+    fs::write(events_path(), serde_json::to_string_pretty(events)?)?;
+    Ok(())
+}
+
+pub fn is_user_event(course: &CourseAction) -> bool {
+    course.id.is_some_and(|id| (EVENT_ID_BASE..EXAM_ID_BASE).contains(&id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_outside_the_event_range_are_rejected() {
+        let entry = |id: u64| format!(r#"{{"id": {}, "title": "Study group", "date": "2026-10-21", "from": "16:00", "to": "18:00"}}"#, id);
+        let data = format!("[{}, {}, {}]", entry(7), entry(MAX_EVENT_ID as u64), entry(3_294_967_296));
+        let (events, skipped) = parse_events(&data).unwrap();
+        assert_eq!(skipped.len(), 2);
+        assert_eq!(events.iter().map(|event| event.id).collect::<Vec<_>>(), vec![7]);
+        assert_eq!(events[0].course_actions()[0].id, Some(EVENT_ID_BASE + 7));
+    }
+
+    #[test]
+    fn a_broken_entry_does_not_wipe_the_others() {
+        let data = r#"[
+            {"id": 1, "title": "Study group", "date": "2026-10-21", "from": "16:00", "to": "18:00"},
+            {"id": 2, "title": "Job", "date": "21.10.2026", "from": "08:00", "to": "12:00"},
+            {"id": 3, "date": "2026-10-22", "from": "08:00", "to": "12:00"},
+            {"id": 4, "title": "Gym", "date": "2026-10-23", "from": "18:00", "to": "19:00"}
+        ]"#;
+        let (events, skipped) = parse_events(data).unwrap();
+        assert_eq!(events.iter().map(|event| event.id).collect::<Vec<_>>(), vec![1, 4]);
+        assert_eq!(skipped.len(), 2);
+        assert!(skipped[0].starts_with("entry 2:"));
+        assert!(parse_events("{").is_err());
+    }
+}
@@ -0,0 +1,186 @@
+use std::fs;
+
+use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime};
+use serde_json::Value;
+
+use crate::cache::{cache_dir, cache_path, strip_diacritics};
+use crate::timetable::{weekday_abbr, CourseAction};
+
+/// Registered exams get ids above the own events, see `EVENT_ID_BASE`.
+pub const EXAM_ID_BASE: u32 = 2_000_000_000;
+
+/// One exam term as STAG lists it for the student.
+pub struct ExamTerm {
+    pub id: u32,
+    pub dept: String,
+    pub abbr: String,
+    /// e.g. "Zkouska" or "Zapocet"
+    pub kind: String,
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+    pub place: String,
+    pub room: String,
+    pub teacher: String,
+    pub occupied: Option<u32>,
+    pub limit: Option<u32>,
+    pub registered: bool,
+    pub note: String,
+}
+
+/// STAG answers numbers and flags either as JSON values or as strings.
+fn number(value: &Value) -> Option<u32> {
+    value.as_u64().and_then(|n| u32::try_from(n).ok()).or_else(|| value.as_str()?.parse().ok())
+}
+
+fn flag(value: &Value) -> bool {
+    value.as_bool().unwrap_or_else(|| matches!(value.as_str(), Some("A") | Some("true")))
+}
+
+fn text(value: &Value) -> String {
+    value.as_str().unwrap_or_default().to_string()
+}
+
+impl ExamTerm {
+    /// Parses one `termin` entry, terms without a date or time are skipped.
+    pub fn from_stag(term: &Value) -> Option<Self> {
+        let date = term["datum"]["value"].as_str().or_else(|| term["datum"].as_str())?;
+        let date = NaiveDate::parse_from_str(date, "%d.%m.%Y").ok()?;
+        let from = NaiveTime::parse_from_str(term["casOd"].as_str()?, "%H:%M").ok()?;
+        let to = term["casDo"]
+            .as_str()
+            .and_then(|to| NaiveTime::parse_from_str(to, "%H:%M").ok())
+            .unwrap_or(from);
+        let teacher = &term["ucitel"];
+        Some(ExamTerm {
+            id: number(&term["termIdno"])?,
+            dept: text(&term["katedra"]),
+            abbr: text(&term["predmet"]),
+            kind: text(&term["typTerminu"]),
+            start: date.and_time(from),
+            end: date.and_time(to),
+            place: text(&term["budova"]),
+            room: text(&term["mistnost"]),
+            teacher: format!("{} {}", text(&teacher["jmeno"]), text(&teacher["prijmeni"])).trim().to_string(),
+            occupied: number(&term["obsazeni"]),
+            limit: number(&term["limit"]),
+            registered: flag(&term["zapsan"]),
+            note: text(&term["poznamka"]),
+        })
+    }
+
+    pub fn is_full(&self) -> bool {
+        matches!((self.occupied, self.limit), (Some(occupied), Some(limit)) if limit > 0 && occupied >= limit)
+    }
+
+    pub fn capacity(&self) -> String {
+        match (self.occupied, self.limit) {
+            (Some(occupied), Some(limit)) => format!("{}/{}", occupied, limit),
+            (Some(occupied), None) => occupied.to_string(),
+            _ => String::new(),
+        }
+    }
+
+    pub fn status(&self) -> &'static str {
+        if self.registered {
+            "registered"
+        } else if self.is_full() {
+            "full"
+        } else {
+            "available"
+        }
+    }
+
+    /// A registered term as a row of the timetable. `name` is the course name,
+    /// which the terms themselves do not carry. `None` for ids past the id range.
+    pub fn course_action(&self, name: Option<String>) -> Option<CourseAction> {
+        let note = [self.kind.as_str(), self.note.as_str()]
+            .iter()
+            .filter(|s| !s.is_empty())
+            .copied()
+            .collect::<Vec<_>>()
+            .join(" - ");
+        Some(CourseAction {
+            id: Some(EXAM_ID_BASE.checked_add(self.id)?),
+            name: Some(name.unwrap_or_else(|| self.abbr.clone())),
+            dept: Some(self.dept.clone()),
+            abbr: Some(self.abbr.clone()),
+            year: None,
+            semester: None,
+            date: Some(self.start.format("%-d.%-m.%Y").to_string()),
+            timeFrom: Some(self.start.format("%H:%M").to_string()),
+            timeTo: Some(self.end.format("%H:%M").to_string()),
+            place: Some(self.place.clone()),
+            room: Some(self.room.clone()),
+            class_type: Some("Exam".to_string()),
+            day: Some(weekday_abbr(self.start.date()).to_string()),
+            weekType: None,
+            weekFrom: None,
+            weekTo: None,
+            note: (!note.is_empty()).then_some(note),
+            contact: Some(self.teacher.clone()),
+            statut: None,
+            teachingTeacherStagId: None,
+        })
+    }
+}
+
+pub fn is_exam(course: &CourseAction) -> bool {
+    course.id.is_some_and(|id| id >= EXAM_ID_BASE)
+}
+
+/// Caches the response like the timetable, stamped with the time of the sync.
+pub fn save_exam_terms(response: &Value) -> Result<(), Box<dyn std::error::Error>> {
+    let mut response = response.clone();
+    response["retrieved_at"] = Value::String(Local::now().naive_local().to_string());
+    fs::create_dir_all(cache_dir())?;
+    fs::write(cache_path("exam_terms.json"), serde_json::to_string_pretty(&response)?)?;
+    Ok(())
+}
+
+/// Cached terms sorted by start, empty before the first successful sync.
+pub fn load_exam_terms() -> Vec<ExamTerm> {
+    let response: Value = fs::read_to_string(cache_path("exam_terms.json"))
+        .ok()
+        .and_then(|data| serde_json::from_str(&strip_diacritics(&data)).ok())
+        .unwrap_or_default();
+    let mut terms: Vec<ExamTerm> = response["termin"]
+        .as_array()
+        .map(|terms| terms.iter().filter_map(ExamTerm::from_stag).collect())
+        .unwrap_or_default();
+    terms.sort_by_key(|term| term.start);
+    terms
+}
+
+/// Registered terms as timetable rows, named after the matching course.
+pub fn registered_exam_actions(courses: &[CourseAction]) -> Vec<CourseAction> {
+    load_exam_terms()
+        .iter()
+        .filter(|term| term.registered)
+        .filter_map(|term| {
+            let name = courses
+                .iter()
+                .find(|c| c.dept.as_deref() == Some(term.dept.as_str()) && c.abbr.as_deref() == Some(term.abbr.as_str()))
+                .and_then(|c| c.name.clone());
+            term.course_action(name)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(id: u64) -> Value {
+        serde_json::json!({"termIdno": id, "datum": {"value": "20.1.2027"}, "casOd": "09:00", "casDo": "11:00"})
+    }
+
+    #[test]
+    fn ids_beyond_u32_are_not_wrapped() {
+        assert!(ExamTerm::from_stag(&term(u32::MAX as u64 + 5)).is_none());
+        let near_limit = ExamTerm::from_stag(&term(u32::MAX as u64)).unwrap();
+        assert_eq!(near_limit.id, u32::MAX);
+        assert!(near_limit.course_action(None).is_none());
+        let fitting = ExamTerm::from_stag(&term(42)).unwrap();
+        assert_eq!(fitting.course_action(None).unwrap().id, Some(EXAM_ID_BASE + 42));
+    }
+}
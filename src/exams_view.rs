@@ -0,0 +1,99 @@
+use chrono::NaiveDateTime;
+use crossterm::event::KeyCode;
+use ratatui::{
+    backend::Backend,
+    layout::{Constraint, Rect},
+    style::Style,
+    widgets::{Block, Borders, Clear, Row, Table, TableState},
+};
+
+use crate::app::{App, View};
+use crate::courses_view::highlighted;
+use crate::exams::ExamTerm;
+use crate::theme::theme;
+use crate::timetable::weekday_abbr;
+
+/// Terms that have not ended yet, in date order.
+pub fn upcoming_terms(terms: &[ExamTerm], now: NaiveDateTime) -> Vec<&ExamTerm> {
+    terms.iter().filter(|term| term.end >= now).collect()
+}
+
+fn term_style(term: &ExamTerm) -> Style {
+    if term.registered {
+        theme().exam
+    } else if term.is_full() {
+        theme().muted
+    } else {
+        Style::default()
+    }
+}
+
+pub fn draw_exams_view<B: Backend>(f: &mut ratatui::Frame<B>, area: Rect, app: &App, now: NaiveDateTime) {
+    let terms = upcoming_terms(&app.exam_terms, now);
+    let registered = terms.iter().filter(|term| term.registered).count();
+    let header_style = theme().header;
+
+    let rows: Vec<Row> = terms
+        .iter()
+        .map(|term| {
+            Row::new(vec![
+                term.status().to_string(),
+                format!("{} {}", weekday_abbr(term.start.date()), term.start.format("%-d.%-m.%Y")),
+                format!("{} - {}", term.start.format("%H:%M"), term.end.format("%H:%M")),
+                format!("{}/{}", term.dept, term.abbr),
+                term.kind.clone(),
+                format!("{} {}", term.place, term.room),
+                term.teacher.clone(),
+                term.capacity(),
+                term.note.clone(),
+            ])
+            .style(term_style(term))
+        })
+        .collect();
+
+    let title = if app.exam_terms.is_empty() {
+        "Exam terms - none cached, [s]ync to fetch them".to_string()
+    } else {
+        format!("Exam terms ({} registered, {} open)", registered, terms.len() - registered)
+    };
+    let table = Table::new(rows)
+        .header(
+            Row::new(vec!["Status", "Date", "Time", "Course", "Type", "Place", "Teacher", "Seats", "Note"])
+                .style(header_style),
+        )
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .widths(&[
+            Constraint::Length(11),
+            Constraint::Length(14),
+            Constraint::Length(14),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Percentage(18),
+            Constraint::Length(6),
+            Constraint::Percentage(20),
+        ]);
+
+    let mut state = TableState::default();
+    state.select(app.view_selected);
+    f.render_widget(Clear, area);
+    f.render_stateful_widget(highlighted(table), area, &mut state);
+}
+
+/// Keys of the exams view. Returns false for keys left to the main key handling.
+pub fn handle_exams_key(app: &mut App, code: KeyCode, now: NaiveDateTime) -> bool {
+    let count = upcoming_terms(&app.exam_terms, now).len();
+    match code {
+        KeyCode::Down | KeyCode::Char('j') if count > 0 => {
+            app.view_selected = Some(app.view_selected.map_or(0, |i| (i + 1).min(count - 1)));
+        }
+        KeyCode::Up | KeyCode::Char('k') if count > 0 => {
+            app.view_selected = Some(app.view_selected.map_or(0, |i| i.saturating_sub(1)));
+        }
+        KeyCode::Char('x') | KeyCode::Backspace | KeyCode::Char('h') => {
+            app.view = View::Table;
+        }
+        _ => return false,
+    }
+    true
+}
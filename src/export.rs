@@ -0,0 +1,385 @@
+use std::path::PathBuf;
+
+use chrono::{Local, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::notes::Notes;
+use crate::timetable::{parse_course_datetime, weekday_abbr, CourseAction, Timetable};
+
+pub const CSV_COLUMNS: [&str; 20] = [
+    "id", "date", "weekday", "start", "end", "dept", "abbr", "name", "type", "type_code",
+    "place", "room", "academic_year", "semester", "week_type", "week_from", "week_to",
+    "teacher_stag_id", "note", "user_note",
+];
+
+/// One class with stable English field names and ISO dates, independent of the API shape.
+#[derive(Serialize, Deserialize)]
+pub struct ExportedClass {
+    pub id: Option<u32>,
+    pub date: Option<String>,
+    pub weekday: Option<String>,
+    pub start: Option<String>,
+    pub end: Option<String>,
+    pub dept: Option<String>,
+    pub abbr: Option<String>,
+    pub name: Option<String>,
+    #[serde(rename = "type")]
+    pub class_type: Option<String>,
+    pub type_code: Option<String>,
+    pub place: Option<String>,
+    pub room: Option<String>,
+    pub academic_year: Option<u32>,
+    pub semester: Option<String>,
+    pub week_type: Option<String>,
+    pub week_from: Option<u32>,
+    pub week_to: Option<u32>,
+    pub teacher_stag_id: Option<u32>,
+    pub note: Option<String>,
+    /// The user's own course and session notes.
+    pub user_note: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportedTimetable {
+    retrieved_at: Option<String>,
+    classes: Vec<ExportedClass>,
+}
+
+impl ExportedTimetable {
+    /// Turns a normalized export back into API-shaped course actions.
+    pub fn into_timetable(self) -> Timetable {
+        let retrieved_at = self
+            .retrieved_at
+            .and_then(|dt| NaiveDateTime::parse_from_str(&dt, "%Y-%m-%dT%H:%M:%S").ok())
+            .map(|dt| dt.format("%Y-%m-%d %H:%M:%S%.f").to_string())
+            .unwrap_or_default();
+        let course_actions = self.classes.into_iter().map(ExportedClass::into_course_action).collect();
+        Timetable::from_course_actions(course_actions, retrieved_at)
+    }
+}
+
+fn normalize_type(code: &str) -> String {
+    match code {
+        "Pr" => "lecture".to_string(),
+        "Cv" => "exercise".to_string(),
+        "Se" => "seminar".to_string(),
+        other => other.to_lowercase(),
+    }
+}
+
+fn denormalize(value: Option<String>, codes: &[(&str, &str)]) -> Option<String> {
+    value.map(|v| {
+        codes
+            .iter()
+            .find(|(_, name)| *name == v)
+            .map_or(v.clone(), |(code, _)| code.to_string())
+    })
+}
+
+fn normalize_semester(code: &str) -> String {
+    match code {
+        "ZS" => "winter".to_string(),
+        "LS" => "summer".to_string(),
+        other => other.to_lowercase(),
+    }
+}
+
+pub fn normalize_week_type(code: &str) -> String {
+    match code {
+        "K" => "every".to_string(),
+        "S" => "even".to_string(),
+        "L" => "odd".to_string(),
+        other => other.to_lowercase(),
+    }
+}
+
+impl ExportedClass {
+    pub fn new(course: &CourseAction, user_note: Option<String>) -> Self {
+        let times = parse_course_datetime(course);
+        Self {
+            id: course.id,
+            date: times.map(|(start, _)| start.format("%Y-%m-%d").to_string()),
+            weekday: times.map(|(start, _)| start.format("%A").to_string().to_lowercase()),
+            start: times.map(|(start, _)| start.format("%Y-%m-%dT%H:%M:%S").to_string()),
+            end: times.map(|(_, end)| end.format("%Y-%m-%dT%H:%M:%S").to_string()),
+            dept: course.dept.clone(),
+            abbr: course.abbr.clone(),
+            name: course.name.clone(),
+            class_type: course.class_type.as_deref().map(normalize_type),
+            type_code: course.class_type.clone(),
+            place: course.place.clone(),
+            room: course.room.clone(),
+            academic_year: course.year.as_deref().and_then(|y| y.parse().ok()),
+            semester: course.semester.as_deref().map(normalize_semester),
+            week_type: course.weekType.as_deref().map(normalize_week_type),
+            week_from: course.weekFrom,
+            week_to: course.weekTo,
+            teacher_stag_id: course.teachingTeacherStagId,
+            note: course.note.clone(),
+            user_note,
+        }
+    }
+
+    fn into_course_action(self) -> CourseAction {
+        let parse = |dt: &Option<String>| {
+            dt.as_deref()
+                .and_then(|dt| NaiveDateTime::parse_from_str(dt, "%Y-%m-%dT%H:%M:%S").ok())
+        };
+        let start = parse(&self.start);
+        let end = parse(&self.end);
+        CourseAction {
+            id: self.id,
+            name: self.name,
+            dept: self.dept,
+            abbr: self.abbr,
+            year: self.academic_year.map(|y| y.to_string()),
+            semester: denormalize(self.semester, &[("ZS", "winter"), ("LS", "summer")]),
+            date: start.map(|dt| dt.format("%-d.%-m.%Y").to_string()),
+            timeFrom: start.map(|dt| dt.format("%H:%M").to_string()),
+            timeTo: end.map(|dt| dt.format("%H:%M").to_string()),
+            place: self.place,
+            room: self.room,
+            class_type: self.type_code.or(self.class_type),
+            day: start.map(|dt| weekday_abbr(dt.date()).to_string()),
+            weekType: denormalize(self.week_type, &[("K", "every"), ("S", "even"), ("L", "odd")]),
+            weekFrom: self.week_from,
+            weekTo: self.week_to,
+            note: self.note,
+            contact: None,
+            statut: None,
+            teachingTeacherStagId: self.teacher_stag_id,
+        }
+    }
+
+    fn csv_fields(&self) -> [String; 20] {
+        let num = |v: Option<u32>| v.map_or(String::new(), |v| v.to_string());
+        let text = |v: &Option<String>| v.clone().unwrap_or_default();
+        [
+            num(self.id),
+            text(&self.date),
+            text(&self.weekday),
+            text(&self.start),
+            text(&self.end),
+            text(&self.dept),
+            text(&self.abbr),
+            text(&self.name),
+            text(&self.class_type),
+            text(&self.type_code),
+            text(&self.place),
+            text(&self.room),
+            num(self.academic_year),
+            text(&self.semester),
+            text(&self.week_type),
+            num(self.week_from),
+            num(self.week_to),
+            num(self.teacher_stag_id),
+            text(&self.note),
+            text(&self.user_note),
+        ]
+    }
+}
+
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn to_csv(courses: &[&CourseAction], notes: &Notes) -> String {
+    let mut out = CSV_COLUMNS.join(",");
+    out.push_str("\r\n");
+    for course in courses {
+        let fields = ExportedClass::new(course, notes.text(course)).csv_fields();
+        let line: Vec<String> = fields.iter().map(|f| csv_escape(f)).collect();
+        out.push_str(&line.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+pub fn to_json(
+    courses: &[&CourseAction],
+    notes: &Notes,
+    retrieved_at: Option<NaiveDateTime>,
+) -> Result<String, serde_json::Error> {
+    let exported = ExportedTimetable {
+        retrieved_at: retrieved_at.map(|dt| dt.format("%Y-%m-%dT%H:%M:%S").to_string()),
+        classes: courses.iter().map(|c| ExportedClass::new(c, notes.text(c))).collect(),
+    };
+    serde_json::to_string_pretty(&exported)
+}
+
+const PRAGUE_VTIMEZONE: &str = "BEGIN:VTIMEZONE\r
+TZID:Europe/Prague\r
+X-LIC-LOCATION:Europe/Prague\r
+BEGIN:DAYLIGHT\r
+TZOFFSETFROM:+0100\r
+TZOFFSETTO:+0200\r
+TZNAME:CEST\r
+DTSTART:19700329T020000\r
+RRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=-1SU\r
+END:DAYLIGHT\r
+BEGIN:STANDARD\r
+TZOFFSETFROM:+0200\r
+TZOFFSETTO:+0100\r
+TZNAME:CET\r
+DTSTART:19701025T030000\r
+RRULE:FREQ=YEARLY;BYMONTH=10;BYDAY=-1SU\r
+END:STANDARD\r
+END:VTIMEZONE\r
+";
+
+/// Escapes TEXT values as required by RFC 5545 section 3.3.11.
+fn ics_escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Folds a content line to at most 75 octets per physical line.
+fn ics_fold(line: &str) -> String {
+    let mut out = String::new();
+    let mut len = 0;
+    for ch in line.chars() {
+        if len + ch.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            len = 1;
+        }
+        out.push(ch);
+        len += ch.len_utf8();
+    }
+    out.push_str("\r\n");
+    out
+}
+
+/// Stable across syncs so re-importing updates events instead of duplicating them.
+/// Actions without an id are told apart by course, type and start time instead.
+pub fn event_uid(course: &CourseAction, start: NaiveDateTime) -> String {
+    match course.id {
+        Some(id) => format!("{}-{}@ujep_tui", id, start.format("%Y%m%d")),
+        None => {
+            let key = format!(
+                "{}-{}-{}",
+                course.abbr.as_deref().unwrap_or(""),
+                course.class_type.as_deref().unwrap_or(""),
+                start.format("%Y%m%dT%H%M")
+            );
+            let key: String = key.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' }).collect();
+            format!("x-{}@ujep_tui", key)
+        }
+    }
+}
+
+pub fn to_ics(courses: &[&CourseAction], notes: &Notes, retrieved_at: Option<NaiveDateTime>) -> String {
+    let stamp = retrieved_at
+        .and_then(|dt| Local.from_local_datetime(&dt).earliest())
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(Utc::now)
+        .format("%Y%m%dT%H%M%SZ")
+        .to_string();
+
+    let mut out = String::new();
+    out.push_str("BEGIN:VCALENDAR\r\n");
+    out.push_str("VERSION:2.0\r\n");
+    out.push_str("PRODID:-//ujep_tui//UJEP Timetable//EN\r\n");
+    out.push_str("CALSCALE:GREGORIAN\r\n");
+    out.push_str("METHOD:PUBLISH\r\n");
+    out.push_str("X-WR-CALNAME:UJEP\r\n");
+    out.push_str("X-WR-TIMEZONE:Europe/Prague\r\n");
+    out.push_str(PRAGUE_VTIMEZONE);
+
+    for course in courses {
+        let Some((start, end)) = parse_course_datetime(course) else {
+            continue;
+        };
+        let summary = format!(
+            "{} ({})",
+            course.name.as_deref().unwrap_or("N/A"),
+            course.class_type.as_deref().unwrap_or("N/A")
+        );
+        let location = [course.place.as_deref(), course.room.as_deref()]
+            .iter()
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let mut description = vec![format!(
+            "{}/{} {}",
+            course.dept.as_deref().unwrap_or("N/A"),
+            course.abbr.as_deref().unwrap_or("N/A"),
+            course.class_type.as_deref().unwrap_or("N/A")
+        )];
+        if let Some(teacher) = course.teachingTeacherStagId {
+            description.push(format!("Teacher STAG ID: {}", teacher));
+        }
+        if let Some(note) = course.note.as_deref().filter(|n| !n.is_empty()) {
+            description.push(format!("Note: {}", note));
+        }
+        if let Some(user_note) = notes.text(course) {
+            description.push(format!("My note: {}", user_note));
+        }
+
+        out.push_str("BEGIN:VEVENT\r\n");
+        out.push_str(&ics_fold(&format!("UID:{}", event_uid(course, start))));
+        out.push_str(&format!("DTSTAMP:{}\r\n", stamp));
+        out.push_str(&format!("LAST-MODIFIED:{}\r\n", stamp));
+        out.push_str(&format!("DTSTART;TZID=Europe/Prague:{}\r\n", start.format("%Y%m%dT%H%M%S")));
+        out.push_str(&format!("DTEND;TZID=Europe/Prague:{}\r\n", end.format("%Y%m%dT%H%M%S")));
+        out.push_str(&ics_fold(&format!("SUMMARY:{}", ics_escape(&summary))));
+        if !location.is_empty() {
+            out.push_str(&ics_fold(&format!("LOCATION:{}", ics_escape(&location))));
+        }
+        out.push_str(&ics_fold(&format!("DESCRIPTION:{}", ics_escape(&description.join("\n")))));
+        if let Some(class_type) = course.class_type.as_deref() {
+            out.push_str(&ics_fold(&format!("CATEGORIES:{}", ics_escape(class_type))));
+        }
+        out.push_str("END:VEVENT\r\n");
+    }
+
+    out.push_str("END:VCALENDAR\r\n");
+    out
+}
+
+/// Where the TUI writes exports: the downloads folder, falling back to home.
+pub fn export_path(extension: &str) -> PathBuf {
+    dirs::download_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(format!("ujep_timetable.{}", extension))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timetable::test_course;
+
+    #[test]
+    fn uid_is_id_and_date() {
+        let course = test_course(42, "21.10.2026", "13:00", "13:50");
+        let (start, _) = parse_course_datetime(&course).unwrap();
+        assert_eq!(event_uid(&course, start), "42-20261021@ujep_tui");
+    }
+
+    #[test]
+    fn uids_without_id_differ_by_course_and_time() {
+        let mut first = test_course(0, "21.10.2026", "13:00", "13:50");
+        first.id = None;
+        first.abbr = Some("OS".to_string());
+        first.class_type = Some("Pr".to_string());
+        let mut second = first.clone();
+        second.timeFrom = Some("15:00".to_string());
+        let mut third = first.clone();
+        third.abbr = Some("ML".to_string());
+
+        let uid = |course: &CourseAction| event_uid(course, parse_course_datetime(course).unwrap().0);
+        assert_eq!(uid(&first), "x-OS-Pr-20261021T1300@ujep_tui");
+        assert_ne!(uid(&first), uid(&second));
+        assert_ne!(uid(&first), uid(&third));
+    }
+}
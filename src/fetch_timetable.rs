@@ -3,11 +3,23 @@ use std::fs::File;
 use std::io::Write;
 use std::path::PathBuf;
 use chrono::{Local, DateTime, Duration};
-use dirs;
 use std::collections::HashSet;
 
+use crate::cache::log_sync_problem;
+use crate::changes::record_changes;
+use crate::config::{load_config, HistoryConfig};
+use crate::exams::save_exam_terms;
+use crate::history::{history_dir, save_snapshot};
+use crate::results::save_results;
+use crate::stag::StagClient;
+
+/// `UJEP_API_URL` points the timetable and exam requests elsewhere, e.g. at a local mock server.
+pub fn api_base_url() -> String {
+    std::env::var("UJEP_API_URL").unwrap_or_else(|_| "https://ujepice.ujep.cz".to_string())
+}
+
 async fn fetch_timetable_data(client: &reqwest::Client, headers: &HeaderMap, stagid: &str, default_year: &str) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
-    let url = format!("https://ujepice.ujep.cz/api/internal/student-timetable?stagId={}&year={}", stagid, default_year);
+    let url = format!("{}/api/internal/student-timetable?stagId={}&year={}", api_base_url(), stagid, default_year);
     let response = client.get(&url)
         .headers(headers.clone())
         .send()
@@ -32,6 +44,12 @@ fn save_timetable_to_file(timetable: &serde_json::Value) -> Result<(), Box<dyn s
     let mut file = File::create(path)?;
     file.write_all(serde_json::to_string_pretty(&timetable_with_datetime)?.as_bytes())?;
 
+    let keep = load_config().map(|config| config.history.keep).unwrap_or(HistoryConfig::default().keep);
+    // The history is an extra, it never fails a sync.
+    if let Err(e) = save_snapshot(&history_dir(), &timetable_with_datetime, keep) {
+        log_sync_problem(&format!("No history snapshot saved: {}", e));
+    }
+
     Ok(())
 }
 
@@ -74,8 +92,47 @@ pub async fn fetch_timetable() -> Result<(), Box<dyn std::error::Error>> {
     
     let timetable_response = fetch_timetable_data(&client, &headers, stagid, &default_year).await?;
 
+    // An expired token answers with an error body, keep the cached timetable instead.
+    if !timetable_response["data"]["courseActions"].is_array() {
+        return Err("Timetable request was rejected, log in again".into());
+    }
+
+    // The previous download is what the changes are measured against. A broken
+    // one is replaced all the same, the changes are just not known this time.
+    if let Ok(previous) = std::fs::read_to_string(get_cache_path("timetable.json")) {
+        if let Err(e) = record_changes(&previous, &timetable_response.to_string()) {
+            log_sync_problem(&format!("Not comparing with the previous timetable: {}", e));
+        }
+    }
     save_timetable_to_file(&timetable_response)?;
 
+    // Exams are a bonus, a failed request keeps the cached terms and the fresh timetable.
+    let stag = StagClient::new(client.clone(), &api_base_url(), headers.clone());
+    if let Ok(terms) = stag.fetch_exam_terms(stagid).await {
+        if let Err(e) = save_exam_terms(&terms) {
+            log_sync_problem(&format!("Exam terms not cached: {}", e));
+        }
+    }
+    // Past years' courses are not in the timetable, their details come with the results.
+    if let Ok(results) = stag.fetch_results(stagid).await {
+        if let Err(e) = save_results(&results) {
+            log_sync_problem(&format!("Results not cached: {}", e));
+        }
+        for entry in results["student_na_predmetu"].as_array().into_iter().flatten() {
+            if let (Some(dept), Some(abbr), Some(year)) = (
+                entry["katedra"].as_str(),
+                entry["zkratka"].as_str(),
+                entry["rok"].as_str().and_then(|y| y.parse::<u32>().ok()).or(entry["rok"].as_u64().map(|y| y as u32))
+            ) {
+                // One failing past course must not break the sync, it is tried
+                // again next time. Nothing is printed, the TUI may be on screen.
+                if matches!(should_fetch_course_details(dept, abbr, &year), Ok(true)) {
+                    let _ = fetch_details(&client, dept, abbr, &year, &headers).await;
+                }
+            }
+        }
+    }
+
     let mut seen_courses = HashSet::new();
 
     if let Some(course_actions) = timetable_response["data"]["courseActions"].as_array() {
@@ -124,7 +181,7 @@ fn should_fetch_course_details(dept: &str, abbr: &str, year: &u32) -> Result<boo
 
 pub async fn fetch_details(client: &reqwest::Client, department: &str, abbr: &str, year: &u32, headers: &HeaderMap) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
     // request to ujepice.ujep.cz/api/stag/courses/get-course-info?katedra=KI&zkratka=ZZD&rok=2021&outputFormat=JSON
-    let url = format!("https://ujepice.ujep.cz/api/stag/courses/get-course-info?katedra={}&zkratka={}&rok={}&outputFormat=JSON", department, abbr, year);
+    let url = format!("{}/api/stag/courses/get-course-info?katedra={}&zkratka={}&rok={}&outputFormat=JSON", api_base_url(), department, abbr, year);
     let mut headers = headers.clone();
     headers.insert("Authorization", HeaderValue::from_static("ApiKey w2HSabPjnn5St73cMPUfqq7TMnDQut3ZExqmX4eQpuxiuNoRyTvZre74LovNiUja"));
     let response = client.get(&url)
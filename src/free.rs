@@ -0,0 +1,125 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Weekday};
+use serde_json::json;
+
+use crate::config::FreeTimeConfig;
+use crate::day_view::gaps;
+use crate::timetable::{parse_course_datetime, CourseAction};
+
+/// A stretch without classes inside the daily bounds.
+pub struct FreeSlot {
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+}
+
+impl FreeSlot {
+    pub fn minutes(&self) -> i64 {
+        (self.end - self.start).num_minutes()
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        json!({
+            "date": self.start.date().format("%Y-%m-%d").to_string(),
+            "start": self.start.format("%Y-%m-%dT%H:%M:%S").to_string(),
+            "end": self.end.format("%Y-%m-%dT%H:%M:%S").to_string(),
+            "minutes": self.minutes(),
+        })
+    }
+}
+
+/// Start and end of each dated course, in no particular order.
+pub fn course_times(courses: &[&CourseAction]) -> Vec<(NaiveDateTime, NaiveDateTime)> {
+    courses.iter().filter_map(|course| parse_course_datetime(course)).collect()
+}
+
+/// Free time from `from` to `to` (both included) between the configured hours,
+/// gaps shorter than the minimum left out. Nothing before `not_before` is free.
+pub fn free_slots(
+    busy: &[(NaiveDateTime, NaiveDateTime)],
+    from: NaiveDate,
+    to: NaiveDate,
+    config: &FreeTimeConfig,
+    not_before: NaiveDateTime,
+) -> Vec<FreeSlot> {
+    let mut slots = Vec::new();
+    for date in from.iter_days().take_while(|date| *date <= to) {
+        if !config.weekends && matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+            continue;
+        }
+        let day_start = date.and_time(config.day_start).max(not_before);
+        let day_end = date.and_time(config.day_end);
+        if day_start >= day_end {
+            continue;
+        }
+
+        // Busy times clipped to the bounds, which themselves act as zero-length classes.
+        let mut day_busy: Vec<(NaiveDateTime, NaiveDateTime)> = busy
+            .iter()
+            .filter(|&&(start, end)| start < day_end && end > day_start)
+            .map(|&(start, end)| (start.max(day_start), end.min(day_end)))
+            .collect();
+        day_busy.push((day_start, day_start));
+        day_busy.push((day_end, day_end));
+        day_busy.sort();
+
+        slots.extend(
+            gaps(&day_busy)
+                .into_iter()
+                .filter(|&(start, end)| end - start >= Duration::minutes(config.min_minutes))
+                .map(|(start, end)| FreeSlot { start, end }),
+        );
+    }
+    slots
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveTime;
+
+    use super::*;
+    use crate::timetable::test_course;
+
+    fn date(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, d).unwrap()
+    }
+
+    fn spans(slots: &[FreeSlot]) -> Vec<String> {
+        slots.iter().map(|slot| format!("{} {}-{}", slot.start.format("%d"), slot.start.format("%H:%M"), slot.end.format("%H:%M"))).collect()
+    }
+
+    fn early() -> NaiveDateTime {
+        date(1).and_hms_opt(0, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn gaps_between_classes_within_the_day() {
+        let (a, b) = (test_course(1, "21.10.2026", "09:00", "10:50"), test_course(2, "21.10.2026", "13:00", "14:50"));
+        let busy = course_times(&[&a, &b]);
+        let slots = free_slots(&busy, date(21), date(21), &FreeTimeConfig::default(), early());
+        assert_eq!(spans(&slots), vec!["21 08:00-09:00", "21 10:50-13:00", "21 14:50-18:00"]);
+        assert_eq!(slots[1].minutes(), 130);
+    }
+
+    #[test]
+    fn short_gaps_and_weekends_are_left_out() {
+        let (a, b) = (test_course(1, "23.10.2026", "08:00", "09:50"), test_course(2, "23.10.2026", "10:10", "17:50"));
+        let busy = course_times(&[&a, &b]);
+        // 23.10.2026 is a Friday, the weekend after it has no slots unless asked for.
+        let slots = free_slots(&busy, date(23), date(25), &FreeTimeConfig::default(), early());
+        assert!(slots.is_empty());
+        let config = FreeTimeConfig { weekends: true, min_minutes: 10, ..FreeTimeConfig::default() };
+        let slots = free_slots(&busy, date(23), date(25), &config, early());
+        assert_eq!(spans(&slots), vec!["23 09:50-10:10", "23 17:50-18:00", "24 08:00-18:00", "25 08:00-18:00"]);
+    }
+
+    #[test]
+    fn classes_outside_the_bounds_and_the_past_are_not_free() {
+        let evening = test_course(1, "21.10.2026", "17:00", "20:00");
+        let config = FreeTimeConfig {
+            day_start: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            ..FreeTimeConfig::default()
+        };
+        let now = date(21).and_hms_opt(12, 30, 0).unwrap();
+        let slots = free_slots(&course_times(&[&evening]), date(20), date(21), &config, now);
+        assert_eq!(spans(&slots), vec!["21 12:30-17:00"]);
+    }
+}
@@ -0,0 +1,102 @@
+use chrono::{Duration, NaiveDateTime};
+use crossterm::event::KeyCode;
+use ratatui::{
+    backend::Backend,
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Clear, Row, Table, TableState},
+};
+
+use crate::app::{App, View};
+use crate::courses_view::highlighted;
+use crate::free::{course_times, free_slots, FreeSlot};
+use crate::status::format_short_duration;
+use crate::theme::theme;
+use crate::timetable::{weekday_abbr, CourseAction};
+
+/// Free slots of the seven days from the view date on.
+fn week_slots(app: &App, displayed: &[&CourseAction], now: NaiveDateTime) -> Vec<FreeSlot> {
+    free_slots(&course_times(displayed), app.view_date, app.view_date + Duration::days(6), &app.free_time, now)
+}
+
+pub fn draw_free_view<B: Backend>(
+    f: &mut ratatui::Frame<B>,
+    area: Rect,
+    app: &App,
+    displayed: &[&CourseAction],
+    now: NaiveDateTime,
+) {
+    let slots = week_slots(app, displayed, now);
+    let header_style = theme().header;
+    let longest = slots.iter().map(FreeSlot::minutes).max().unwrap_or(0).max(1);
+
+    let rows: Vec<Row> = slots
+        .iter()
+        .map(|slot| {
+            let date = slot.start.date();
+            let style = if date == now.date() {
+                theme().good.add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            Row::new(vec![
+                format!("{} {}", weekday_abbr(date), date.format("%-d.%-m.%Y")),
+                format!("{} - {}", slot.start.format("%H:%M"), slot.end.format("%H:%M")),
+                format_short_duration(slot.end - slot.start),
+                "█".repeat((slot.minutes() * 30 / longest).max(1) as usize),
+            ])
+            .style(style)
+        })
+        .collect();
+
+    let total: i64 = slots.iter().map(FreeSlot::minutes).sum();
+    let title = format!(
+        "Free time {} - {}, {}-{}, at least {} min: {} ([</>] week)",
+        app.view_date.format("%-d.%-m."),
+        (app.view_date + Duration::days(6)).format("%-d.%-m.%Y"),
+        app.free_time.day_start.format("%H:%M"),
+        app.free_time.day_end.format("%H:%M"),
+        app.free_time.min_minutes,
+        format_short_duration(Duration::minutes(total))
+    );
+    let table = Table::new(rows)
+        .header(Row::new(vec!["Date", "Free", "Length", ""]).style(header_style))
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .widths(&[
+            Constraint::Length(14),
+            Constraint::Length(14),
+            Constraint::Length(8),
+            Constraint::Length(30),
+        ]);
+
+    let mut state = TableState::default();
+    state.select(app.view_selected);
+    f.render_widget(Clear, area);
+    f.render_stateful_widget(highlighted(table), area, &mut state);
+}
+
+/// Keys of the free time view. Returns false for keys left to the main key handling.
+pub fn handle_free_key(app: &mut App, displayed: &[&CourseAction], code: KeyCode, now: NaiveDateTime) -> bool {
+    let count = week_slots(app, displayed, now).len();
+    match code {
+        KeyCode::Down | KeyCode::Char('j') if count > 0 => {
+            app.view_selected = Some(app.view_selected.map_or(0, |i| (i + 1).min(count - 1)));
+        }
+        KeyCode::Up | KeyCode::Char('k') if count > 0 => {
+            app.view_selected = Some(app.view_selected.map_or(0, |i| i.saturating_sub(1)));
+        }
+        KeyCode::Left | KeyCode::Char('<') | KeyCode::PageUp => {
+            app.view_date -= Duration::days(7);
+            app.view_selected = None;
+        }
+        KeyCode::Right | KeyCode::Char('>') | KeyCode::PageDown => {
+            app.view_date += Duration::days(7);
+            app.view_selected = None;
+        }
+        KeyCode::Char('f') | KeyCode::Backspace | KeyCode::Char('h') => {
+            app.view = View::Table;
+        }
+        _ => return false,
+    }
+    true
+}
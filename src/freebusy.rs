@@ -0,0 +1,83 @@
+use std::fs;
+use std::path::Path;
+
+use chrono::{Local, NaiveDate, NaiveDateTime, Timelike};
+use serde::{Deserialize, Serialize};
+
+/// A shareable timetable that only tells when someone is busy, without
+/// course names, rooms or anything else about the classes.
+#[derive(Serialize, Deserialize)]
+pub struct FreeBusy {
+    /// Who the file is from, for the summary when importing.
+    #[serde(default)]
+    pub name: Option<String>,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub generated_at: NaiveDateTime,
+    pub busy: Vec<Busy>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct Busy {
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+}
+
+/// Sorted intervals with overlapping and adjacent ones joined, so back-to-back
+/// classes cannot be told apart.
+pub fn merge_intervals(times: &[(NaiveDateTime, NaiveDateTime)]) -> Vec<(NaiveDateTime, NaiveDateTime)> {
+    let mut sorted = times.to_vec();
+    sorted.sort();
+    let mut merged: Vec<(NaiveDateTime, NaiveDateTime)> = Vec::new();
+    for (start, end) in sorted {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+impl FreeBusy {
+    /// Busy intervals of the days from `from` to `to`, both included.
+    pub fn new(times: &[(NaiveDateTime, NaiveDateTime)], from: NaiveDate, to: NaiveDate, name: Option<String>) -> Self {
+        let in_range: Vec<_> = times
+            .iter()
+            .filter(|(start, end)| *end > from.and_hms_opt(0, 0, 0).unwrap() && start.date() <= to)
+            .copied()
+            .collect();
+        FreeBusy {
+            name,
+            from,
+            to,
+            generated_at: Local::now().naive_local().with_nanosecond(0).unwrap(),
+            busy: merge_intervals(&in_range).into_iter().map(|(start, end)| Busy { start, end }).collect(),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        self.name.clone().unwrap_or_else(|| "unnamed".to_string())
+    }
+
+    pub fn times(&self) -> Vec<(NaiveDateTime, NaiveDateTime)> {
+        self.busy.iter().map(|busy| (busy.start, busy.end)).collect()
+    }
+}
+
+pub fn load_free_busy(path: &Path) -> Result<FreeBusy, Box<dyn std::error::Error>> {
+    let data = fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    let free_busy: FreeBusy = serde_json::from_str(&data)
+        .map_err(|e| format!("{} is not a free/busy file: {}", path.display(), e))?;
+    if free_busy.busy.iter().any(|busy| busy.end < busy.start) {
+        return Err(format!("{} has a busy interval ending before it starts", path.display()).into());
+    }
+    Ok(free_busy)
+}
+
+/// The days every file covers, `None` when they do not share any.
+pub fn common_range(files: &[FreeBusy]) -> Option<(NaiveDate, NaiveDate)> {
+    let from = files.iter().map(|file| file.from).max()?;
+    let to = files.iter().map(|file| file.to).min()?;
+    (from <= to).then_some((from, to))
+}
+
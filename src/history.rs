@@ -0,0 +1,114 @@
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{Local, NaiveDateTime};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde_json::Value;
+
+use crate::cache::cache_path;
+
+const NAME_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// One stored download, `name` is its timestamp, e.g. `20261018-181756`.
+pub struct Snapshot {
+    pub name: String,
+    pub taken_at: NaiveDateTime,
+    pub path: PathBuf,
+}
+
+pub fn history_dir() -> PathBuf {
+    cache_path("history")
+}
+
+/// Snapshots stored in `dir`, oldest first.
+pub fn list_snapshots(dir: &Path) -> Vec<Snapshot> {
+    let mut snapshots: Vec<Snapshot> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let name = path.file_name()?.to_str()?.strip_suffix(".json.gz")?.to_string();
+            let taken_at = NaiveDateTime::parse_from_str(&name, NAME_FORMAT).ok()?;
+            Some(Snapshot { name, taken_at, path })
+        })
+        .collect();
+    snapshots.sort_by_key(|snapshot| snapshot.taken_at);
+    snapshots
+}
+
+pub fn read_snapshot(snapshot: &Snapshot) -> Result<String, Box<dyn std::error::Error>> {
+    let mut data = String::new();
+    GzDecoder::new(fs::File::open(&snapshot.path)?).read_to_string(&mut data)?;
+    Ok(data)
+}
+
+/// Stores a fresh download in `dir` unless it has the same classes as the newest snapshot,
+/// then drops the oldest ones beyond `keep`. Keeping none turns the history off.
+pub fn save_snapshot(dir: &Path, timetable: &Value, keep: usize) -> Result<(), Box<dyn std::error::Error>> {
+    if keep == 0 {
+        return Ok(());
+    }
+    let snapshots = list_snapshots(dir);
+    // A damaged newest snapshot counts as different, the fresh one goes next to it.
+    let previous = snapshots
+        .last()
+        .and_then(|newest| read_snapshot(newest).ok())
+        .and_then(|data| serde_json::from_str::<Value>(&data).ok());
+    if previous.is_some_and(|previous| previous["data"]["courseActions"] == timetable["data"]["courseActions"]) {
+        return Ok(());
+    }
+
+    fs::create_dir_all(dir)?;
+    let name = Local::now().naive_local().format(NAME_FORMAT).to_string();
+    let file = fs::File::create(dir.join(format!("{}.json.gz", name)))?;
+    let mut encoder = GzEncoder::new(file, Compression::default());
+    encoder.write_all(serde_json::to_string(timetable)?.as_bytes())?;
+    encoder.finish()?;
+
+    let snapshots = list_snapshots(dir);
+    // Left over files are retried on the next save.
+    for snapshot in snapshots.iter().take(snapshots.len().saturating_sub(keep)) {
+        let _ = fs::remove_file(&snapshot.path);
+    }
+    Ok(())
+}
+
+/// The newest snapshot whose timestamp starts with `query`, separators ignored,
+/// so `2026-10-12` picks the last download of that day.
+pub fn find_snapshot<'s>(snapshots: &'s [Snapshot], query: &str) -> Option<&'s Snapshot> {
+    let digits = |s: &str| s.chars().filter(char::is_ascii_digit).collect::<String>();
+    let query = digits(query);
+    if query.is_empty() {
+        return None;
+    }
+    snapshots.iter().rev().find(|snapshot| digits(&snapshot.name).starts_with(&query))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn damaged_newest_snapshot_does_not_stop_saving() {
+        let dir = std::env::temp_dir().join(format!("ujep_tui_history_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("20200101-000000.json.gz"), b"not gzip").unwrap();
+
+        let timetable = serde_json::json!({"data": {"courseActions": [{"id": 1}]}});
+        save_snapshot(&dir, &timetable, 5).unwrap();
+        let snapshots = list_snapshots(&dir);
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(read_snapshot(&snapshots[1]).unwrap(), timetable.to_string());
+
+        // Unchanged classes add nothing, retention drops the damaged one.
+        save_snapshot(&dir, &timetable, 5).unwrap();
+        assert_eq!(list_snapshots(&dir).len(), 2);
+        save_snapshot(&dir, &serde_json::json!({"data": {"courseActions": []}}), 1).unwrap();
+        assert_eq!(list_snapshots(&dir).len(), 1);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
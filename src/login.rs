@@ -14,11 +14,13 @@ use crossterm::{
 use ratatui::{
     backend::CrosstermBackend,
     layout::{Alignment, Constraint, Direction, Layout},
-    style::{Color, Style},
     widgets::{Block, Borders, Paragraph, Clear},
     Terminal,
 };
 
+use crate::fetch_timetable::api_base_url;
+use crate::theme::theme;
+
 #[derive(Debug, PartialEq)]
 enum InputMode {
     Username,
@@ -152,7 +154,7 @@ async fn login(client: &reqwest::Client, headers: &HeaderMap) -> Result<serde_js
         "password": password
     });
 
-    let response = client.post("https://ujepice.ujep.cz/api/internal/login/stag")
+    let response = client.post(format!("{}/api/internal/login/stag", api_base_url()))
         .json(&body)
         .headers(headers.clone())
         .send()
@@ -205,9 +207,9 @@ fn prompt_for_credentials() -> Result<(String, String), Box<dyn std::error::Erro
             let vertical_layout = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(top_space.into()),
+                Constraint::Length(top_space),
                 Constraint::Length(12),       
-                Constraint::Length(bottom_space.into()),
+                Constraint::Length(bottom_space),
                 Constraint::Length(3),        
             ])
             .split(size);
@@ -223,9 +225,9 @@ fn prompt_for_credentials() -> Result<(String, String), Box<dyn std::error::Erro
             let horizontal_layout = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([
-                Constraint::Length(left_space.into()),
+                Constraint::Length(left_space),
                 Constraint::Length(40),
-                Constraint::Length(right_space.into()),
+                Constraint::Length(right_space),
             ])
             .split(center_rect);
         
@@ -261,11 +263,7 @@ fn prompt_for_credentials() -> Result<(String, String), Box<dyn std::error::Erro
             let username_block = Paragraph::new(username.as_str())
             .block(Block::default().borders(Borders::ALL).title("Username"))
             .style(
-                Style::default().fg(if input_mode == InputMode::Username {
-                Color::Yellow
-                } else {
-                Color::White
-                }),
+                if input_mode == InputMode::Username { theme().focus } else { theme().text },
             );
             f.render_widget(username_block, login_chunks[0]);
         
@@ -273,11 +271,7 @@ fn prompt_for_credentials() -> Result<(String, String), Box<dyn std::error::Erro
             let password_block = Paragraph::new(password_display)
             .block(Block::default().borders(Borders::ALL).title("Password"))
             .style(
-                Style::default().fg(if input_mode == InputMode::Password {
-                Color::Yellow
-                } else {
-                Color::White
-                }),
+                if input_mode == InputMode::Password { theme().focus } else { theme().text },
             );
             f.render_widget(password_block, login_chunks[1]);
 
@@ -285,11 +279,7 @@ fn prompt_for_credentials() -> Result<(String, String), Box<dyn std::error::Erro
             let hint_paragraph = Paragraph::new(login_hint)
             .alignment(Alignment::Center)
             .style(
-                Style::default().fg(if input_mode == InputMode::Password {
-                Color::Yellow
-                } else {
-                Color::White
-                }),
+                if input_mode == InputMode::Password { theme().focus } else { theme().text },
             );
             f.render_widget(hint_paragraph, login_chunks[2]);
 
@@ -305,11 +295,7 @@ fn prompt_for_credentials() -> Result<(String, String), Box<dyn std::error::Erro
             let offline_label = Paragraph::new("Offline Mode")
                 .block(Block::default().borders(Borders::ALL))
                 .style(
-                Style::default().fg(if input_mode == InputMode::OfflineMode {
-                    Color::Yellow
-                } else {
-                    Color::White
-                }),
+                if input_mode == InputMode::OfflineMode { theme().focus } else { theme().text },
                 );
             f.render_widget(offline_label, bottom_layout[1]);
             }
@@ -319,13 +305,7 @@ fn prompt_for_credentials() -> Result<(String, String), Box<dyn std::error::Erro
             match input_mode {
                 InputMode::Username => match key.code {
                     KeyCode::Enter | KeyCode::Tab => {
-                        
-                        
-                        if offline_mode_available {
-                            input_mode = InputMode::Password;
-                        } else {
-                            input_mode = InputMode::Password;
-                        }
+                        input_mode = InputMode::Password;
                     }
                     KeyCode::Char(c) => username.push(c),
                     KeyCode::Backspace => {
@@ -371,7 +351,7 @@ fn prompt_for_credentials() -> Result<(String, String), Box<dyn std::error::Erro
 
 async fn fetch_profile(client: &reqwest::Client, headers: &HeaderMap) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
     //println!("Sending profile request...");
-    let response = client.get("https://ujepice.ujep.cz/api/profile/v2")
+    let response = client.get(format!("{}/api/profile/v2", api_base_url()))
         .headers(headers.clone())
         .send()
         .await?
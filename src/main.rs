@@ -1,65 +1,73 @@
 mod timetable;
 mod app;
+mod attendance;
 mod ui;
 mod fetch_timetable;
+mod history;
 mod login;
+mod month_view;
+mod notes;
+mod places;
+mod places_view;
+mod cache;
+mod changes;
+mod cli;
+mod commands;
+mod config;
+mod conflicts;
+mod conflicts_view;
+mod courses_view;
+mod daemon;
+mod day_view;
+mod events;
+mod exams;
+mod exams_view;
+mod export;
+mod free;
+mod free_view;
+mod freebusy;
+mod reminders;
+mod results;
+mod results_view;
+mod search;
+mod stag;
+mod status;
+mod theme;
+mod travel;
+mod week_view;
 
-use std::fs;
 use std::io;
-use std::collections::HashSet;
+use std::process::ExitCode;
+use clap::Parser;
 use crossterm::{
     execute,
     terminal::{enable_raw_mode, disable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::backend::CrosstermBackend;
 use ratatui::Terminal;
-use serde::{Deserialize, Serialize};
-use dirs::cache_dir;
 
-use crate::timetable::Timetable;
 use crate::app::App;
+use crate::attendance::load_attendance;
+use crate::events::is_user_event;
+use crate::exams::{is_exam, load_exam_terms};
+use crate::freebusy::{common_range, load_free_busy, merge_intervals, FreeBusy};
+use crate::notes::Notes;
+use crate::places::Directory;
+use crate::cache::{has_cached_timetable, load_ignored_ids, load_timetable, load_timetable_file, retrieved_at, save_ignored_ids};
+use crate::changes::{changed_sessions, load_changes};
+use crate::cli::{Cli, Command, TuiArgs};
+use crate::config::load_config;
+use crate::theme::{set_theme, Theme};
+use crate::reminders::Reminders;
+use crate::results::load_results;
 use crate::ui::run_app;
 use crate::fetch_timetable::fetch_timetable;
 use crate::login::run_login;
+use crate::timetable::parse_course_datetime;
 
-#[derive(Serialize, Deserialize)]
-struct IgnoredIds {
-    ids: HashSet<u32>,
-}
-
-fn load_ignored_ids() -> HashSet<u32> {
-    if let Some(mut path) = cache_dir() {
-        path.push("ujep_tui");
-        path.push("ignored_ids.json");
-        if let Ok(data) = fs::read_to_string(&path) {
-            if let Ok(ignored_ids) = serde_json::from_str::<IgnoredIds>(&data) {
-                return ignored_ids.ids;
-            }
-        }
-    }
-    HashSet::new()
-}
-
-fn save_ignored_ids(ignored_ids: &HashSet<u32>) {
-    if let Some(mut path) = cache_dir() {
-        path.push("ujep_tui");
-        fs::create_dir_all(&path).unwrap();
-        path.push("ignored_ids.json");
-        let ignored_ids = IgnoredIds {
-            ids: ignored_ids.clone(),
-        };
-        if let Ok(data) = serde_json::to_string(&ignored_ids) {
-            let _ = fs::write(path, data);
-        }
-    }
-}
 fn offline_fallback() -> Result<(), Box<dyn std::error::Error>> {
     // Check if timetable exists in cache
-    let mut path = cache_dir().unwrap();
-    path.push("ujep_tui");
-    path.push("timetable.json");
-    
-    if path.exists() {
+    if has_cached_timetable() {
         Ok(()) // File exists, we can continue with cached data
     } else {
         Err("No cached timetable available while offline".into())
@@ -67,55 +75,67 @@ fn offline_fallback() -> Result<(), Box<dyn std::error::Error>> {
 }
 
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Expect one command-line argument: the path to the timetable JSON file.
-    // Fetch the timetable if the file does not exist.
+async fn main() -> Result<ExitCode, Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+
+    match cli.command.unwrap_or(Command::Tui(TuiArgs::default())) {
+        Command::Tui(args) => run_tui(&args).await,
+        Command::Sync => commands::sync().await,
+        Command::Login => commands::login().await,
+        Command::Logout => commands::logout(),
+        Command::List(args) => commands::list(&args),
+        Command::Agenda(args) => commands::agenda(&args),
+        Command::Next(args) => commands::next(&args),
+        Command::Export(args) => commands::export(&args),
+        Command::Ignore(args) => commands::ignore(&args),
+        Command::Event(args) => commands::event(&args),
+        Command::Exams(args) => commands::exams(&args),
+        Command::Results => commands::results(),
+        Command::History => commands::history(),
+        Command::Diff(args) => commands::diff(&args),
+        Command::Free(args) => commands::free(&args),
+        Command::Freebusy(args) => commands::free_busy(&args),
+        Command::Places(args) => commands::places(&args),
+        Command::Conflicts(args) => {
+            // A non-zero status lets scripts and cron jobs react without parsing the output.
+            if commands::conflicts(&args)? {
+                return Ok(ExitCode::FAILURE);
+            }
+            Ok(())
+        }
+        Command::Remind(args) => commands::remind(&args).await,
+        Command::Daemon(args) => daemon::run_daemon(&args).await,
+    }?;
+    Ok(ExitCode::SUCCESS)
+}
+
+async fn run_tui(args: &TuiArgs) -> Result<(), Box<dyn std::error::Error>> {
+    // Fail early on a broken file or config, before taking over the terminal.
+    if let Some(path) = &args.file {
+        load_timetable_file(path)?;
+    }
+    let config = load_config()?;
+    set_theme(Theme::from_config(&config.theme, args.theme.as_deref())?);
+    Directory::load()?;
+    let group = args.free_busy.iter().map(|path| load_free_busy(path)).collect::<Result<Vec<_>, _>>()?;
+    let group_range = common_range(&group);
+    if !group.is_empty() && group_range.is_none() {
+        return Err("The free/busy files do not cover any common days".into());
+    }
+    let group_busy = merge_intervals(&group.iter().flat_map(FreeBusy::times).collect::<Vec<_>>());
+
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    display_loading_widget()?;
-    
-    // Try to login and fetch timetable, fallback to offline mode if network errors occur
-    let online_mode = match run_login().await {
-        Ok(_) => {
-            match fetch_timetable().await {
-                Ok(_) => true,
-                Err(e) => {
-                    // Check if error is a network error
-                    if e.to_string().contains("failed to lookup address") || 
-                       e.to_string().contains("dns error") {
-                        offline_fallback()?;
-                        false
-                    } else {
-                        return Err(e);
-                    }
-                }
-            }
-        },
-        Err(e) => {
-            // Check if error is a network error
-            if e.to_string().contains("offline mode") {
-                offline_fallback()?;
-                false
-            } 
-            else if e.to_string().contains("failed to lookup address") || 
-            e.to_string().contains("dns error") 
-            {
-                disable_raw_mode()?;
-                execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-                terminal.show_cursor()?;
-                return Err("Cannot authenticate user, network is down.".into());
-            }
-            else {
-                disable_raw_mode()?;
-                execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-                terminal.show_cursor()?;
-                return Err(e);
-            }
-        }
+    // Viewing a file needs neither the network nor a login.
+    let online_mode = if args.file.is_some() {
+        false
+    } else {
+        display_loading_widget()?;
+        login_and_fetch(&mut terminal).await?
     };
 
     // disable_raw_mode()?;
@@ -124,24 +144,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let mut last: Option<bool> = None;
 
+    // Results are only refreshed by syncs, which restart the loop below.
+    let mut results = Vec::new();
+
     loop {
-        // Read and parse the JSON file.
-        let mut path = cache_dir().unwrap();
-        path.push("ujep_tui");
-        path.push("timetable.json");
-        let json_data = fs::read_to_string(&path)?;
-        let replacements = [
-            ("Á", "A"), ("á", "a"), ("Č", "C"), ("č", "c"), ("Ď", "D"), ("ď", "d"), 
-            ("É", "E"), ("é", "e"), ("Ě", "E"), ("ě", "e"), ("Í", "I"), ("í", "i"), 
-            ("Ň", "N"), ("ň", "n"), ("Ó", "O"), ("ó", "o"), ("Ř", "R"), ("ř", "r"), 
-            ("Š", "S"), ("š", "s"), ("Ť", "T"), ("ť", "t"), ("Ú", "U"), ("ú", "u"), 
-            ("Ů", "U"), ("ů", "u"), ("Ý", "Y"), ("ý", "y"), ("Ž", "Z"), ("ž", "z")
-        ];
-        let mut json_data = json_data;
-        for &(from, to) in &replacements {
-            json_data = json_data.replace(from, to);
+        if args.file.is_none() {
+            results = load_results();
         }
-        let timetable: Timetable = serde_json::from_str(&json_data)?;
+        // Read and parse the cached timetable, or the file given on the command line.
+        let timetable = match &args.file {
+            Some(path) => load_timetable_file(path)?,
+            None => load_timetable()?,
+        };
 
         let courses: Vec<_> = timetable.data.courseActions
             .iter()
@@ -151,13 +165,36 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         // Load ignored IDs from cache.
         let ignored_ids = load_ignored_ids();
 
-        // generate a NaiveDateTime
-        let dt = timetable.retrieved_at;
-        let dt = chrono::NaiveDateTime::parse_from_str(&dt, "%Y-%m-%d %H:%M:%S%.f").unwrap();
-
         // Create our app and sort courses by start time.
         let mut app = App::new(courses, Some(ignored_ids));
-        app.last_update = Some(dt);
+        app.last_update = retrieved_at(&timetable);
+        app.read_only = args.file.is_some();
+        app.attendance = load_attendance();
+        app.attendance_config = config.attendance.clone();
+        app.free_time = config.free_time.clone();
+        app.travel = config.travel.clone();
+        // Checked before starting, a file broken since then only loses the own entries.
+        app.places = Directory::load().unwrap_or_default();
+        app.group_busy = group_busy.clone();
+        app.group_names = group.iter().map(FreeBusy::label).collect();
+        app.group_range = group_range;
+        app.notes = Notes::load();
+        if !app.read_only {
+            app.exam_terms = load_exam_terms();
+            app.results = &results;
+            app.changes = load_changes();
+            app.changed_sessions = changed_sessions(&app.changes);
+            app.show_changes = !app.changes.is_empty();
+        }
+        app.marked_days = app
+            .courses
+            .iter()
+            .filter(|c| is_user_event(c) || is_exam(c))
+            .filter_map(|c| parse_course_datetime(c).map(|(start, _)| start.date()))
+            .collect();
+        if !app.read_only && config.reminders.command.is_some() {
+            app.reminders = Some(Reminders::new(config.reminders.clone()));
+        }
 
         if last.is_none()
         {
@@ -192,7 +229,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         terminal.show_cursor()?;
 
         // Save ignored IDs to cache.
-        save_ignored_ids(&app.ignored_ids);
+        if !app.read_only {
+            save_ignored_ids(&app.ignored_ids);
+        }
 
         if let Err(err) = res {
             if err.kind() == io::ErrorKind::Interrupted && err.to_string() == "forced refresh" {
@@ -223,6 +262,51 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+async fn login_and_fetch(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    // Try to login and fetch timetable, fallback to offline mode if network errors occur
+    let online_mode = match run_login().await {
+        Ok(_) => {
+            match fetch_timetable().await {
+                Ok(_) => true,
+                Err(e) => {
+                    // Check if error is a network error
+                    if e.to_string().contains("failed to lookup address") || 
+                       e.to_string().contains("dns error") {
+                        offline_fallback()?;
+                        false
+                    } else {
+                        return Err(e);
+                    }
+                }
+            }
+        },
+        Err(e) => {
+            // Check if error is a network error
+            if e.to_string().contains("offline mode") {
+                offline_fallback()?;
+                false
+            } 
+            else if e.to_string().contains("failed to lookup address") || 
+            e.to_string().contains("dns error") 
+            {
+                disable_raw_mode()?;
+                execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+                terminal.show_cursor()?;
+                return Err("Cannot authenticate user, network is down.".into());
+            }
+            else {
+                disable_raw_mode()?;
+                execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+                terminal.show_cursor()?;
+                return Err(e);
+            }
+        }
+    };
+    Ok(online_mode)
+}
+
 fn display_loading_widget() -> Result<(), Box<dyn std::error::Error>> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, Duration, Months, NaiveDate, NaiveDateTime};
+use crossterm::event::KeyCode;
+use ratatui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::app::{App, View};
+use crate::events::is_user_event;
+use crate::exams::is_exam;
+use crate::theme::theme;
+use crate::timetable::{parse_course_datetime, CourseAction};
+use crate::week_view::week_monday;
+
+const WEEKDAYS: [&str; 7] = ["Po", "Ut", "St", "Ct", "Pa", "So", "Ne"];
+
+/// Scheduled contact hours per day, own events and exams are only marked.
+pub fn contact_hours(displayed: &[&CourseAction]) -> HashMap<NaiveDate, f64> {
+    let mut hours = HashMap::new();
+    for course in displayed.iter().filter(|c| !is_user_event(c) && !is_exam(c)) {
+        if let Some((start, end)) = parse_course_datetime(course) {
+            *hours.entry(start.date()).or_insert(0.0) += (end - start).num_minutes() as f64 / 60.0;
+        }
+    }
+    hours
+}
+
+/// Style of a day with `hours` of classes, `None` for a free day.
+pub fn heat_style(hours: f64) -> Option<Style> {
+    let heat = &theme().heat;
+    match hours {
+        h if h <= 0.0 => None,
+        h if h < 2.0 => Some(heat[0]),
+        h if h < 4.0 => Some(heat[1]),
+        h if h < 6.0 => Some(heat[2]),
+        _ => Some(heat[3]),
+    }
+}
+
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap()
+}
+
+pub fn draw_month_view<B: Backend>(
+    f: &mut ratatui::Frame<B>,
+    area: Rect,
+    app: &App,
+    displayed: &[&CourseAction],
+    now: NaiveDateTime,
+) {
+    let first = first_of_month(app.view_date);
+    let grid_start = week_monday(first);
+    let hours = contact_hours(displayed);
+
+    let title = format!(
+        "{} ([</>] month, [Enter] day, [g]o to table)",
+        first.format("%B %Y")
+    );
+    f.render_widget(Clear, area);
+    f.render_widget(Block::default().borders(Borders::ALL).title(title), area);
+
+    let inner = Rect {
+        x: area.x + 1,
+        y: area.y + 1,
+        width: area.width.saturating_sub(2),
+        height: area.height.saturating_sub(2),
+    };
+    if inner.height < 11 || inner.width < 40 {
+        return;
+    }
+
+    // Enough rows for any month, the last one may stay empty.
+    let weeks = 6;
+    let week_width = 10;
+    let cell_width = (inner.width - week_width) / 7;
+    // Leaves room for the legend and the boxes drawn over the bottom of the screen.
+    let cell_height = ((inner.height - 5) / weeks).max(1);
+
+    let header_style = theme().header;
+    for (i, name) in WEEKDAYS.iter().enumerate() {
+        let rect = Rect { x: inner.x + i as u16 * cell_width, y: inner.y, width: cell_width, height: 1 };
+        f.render_widget(Paragraph::new(*name).alignment(Alignment::Center).style(header_style), rect);
+    }
+    let week_x = inner.x + 7 * cell_width;
+    let week_header = Rect { x: week_x, y: inner.y, width: week_width, height: 1 };
+    f.render_widget(Paragraph::new("Week").alignment(Alignment::Center).style(header_style), week_header);
+
+    for week in 0..weeks {
+        let monday = grid_start + Duration::days(7 * week as i64);
+        if week > 0 && monday.month() != first.month() {
+            break;
+        }
+        let y = inner.y + 1 + week * cell_height;
+        let mut week_hours = 0.0;
+
+        for day in 0..7 {
+            let date = monday + Duration::days(day);
+            let day_hours = hours.get(&date).copied().unwrap_or(0.0);
+            let in_month = date.month() == first.month();
+            let rect = Rect {
+                x: inner.x + day as u16 * cell_width,
+                y,
+                width: cell_width.saturating_sub(1),
+                height: cell_height.saturating_sub(1).max(1),
+            };
+
+            let mut style = Style::default();
+            if !in_month {
+                style = theme().muted;
+            } else {
+                week_hours += day_hours;
+                if let Some(heat) = heat_style(day_hours) {
+                    style = heat;
+                }
+            }
+            if date == app.view_date {
+                style = style.patch(theme().selected);
+            }
+
+            let number_style = if date == now.date() {
+                theme().today
+            } else {
+                Style::default().add_modifier(Modifier::BOLD)
+            };
+            let mut first_line = vec![Span::styled(format!("{:>2}", date.day()), number_style)];
+            if app.marked_days.contains(&date) {
+                first_line.push(Span::styled(" !", theme().accent.add_modifier(Modifier::BOLD)));
+            }
+            let mut lines = vec![Spans::from(first_line)];
+            if in_month && day_hours > 0.0 {
+                lines.push(Spans::from(format!("{:.1}h", day_hours)));
+            }
+
+            f.render_widget(Clear, rect);
+            f.render_widget(Paragraph::new(lines).style(style), rect);
+        }
+
+        // Weeks are compared against a 5 day week, so the colours line up with the days.
+        let rect = Rect { x: week_x, y, width: week_width, height: cell_height.saturating_sub(1).max(1) };
+        let mut style = Style::default().add_modifier(Modifier::BOLD);
+        if let Some(heat) = heat_style(week_hours / 5.0) {
+            style = heat.add_modifier(Modifier::BOLD);
+        }
+        let text = format!("{:.1}h", week_hours);
+        f.render_widget(Paragraph::new(text).alignment(Alignment::Center).style(style), rect);
+    }
+
+    let mut legend = vec![Span::raw("Contact hours: ")];
+    for (label, hours) in [(" <2h ", 1.0), (" 2-4h ", 3.0), (" 4-6h ", 5.0), (" 6h+ ", 7.0)] {
+        legend.push(Span::styled(label, heat_style(hours).unwrap()));
+        legend.push(Span::raw(" "));
+    }
+    legend.push(Span::styled(" ! ", theme().accent.add_modifier(Modifier::BOLD)));
+    legend.push(Span::raw("exam or own event"));
+    let legend_rect = Rect { x: inner.x, y: inner.y + 1 + weeks * cell_height, width: inner.width, height: 1 };
+    f.render_widget(Paragraph::new(Spans::from(legend)), legend_rect);
+}
+
+/// Keys of the month view. `shown` are the rows of the main table, for jumping into it.
+pub fn handle_month_key(app: &mut App, shown: &[&CourseAction], code: KeyCode) -> bool {
+    match code {
+        KeyCode::Left => app.view_date -= Duration::days(1),
+        KeyCode::Right => app.view_date += Duration::days(1),
+        KeyCode::Up | KeyCode::Char('k') => app.view_date -= Duration::days(7),
+        KeyCode::Down | KeyCode::Char('j') => app.view_date += Duration::days(7),
+        KeyCode::Char('<') | KeyCode::PageUp => app.view_date = app.view_date - Months::new(1),
+        KeyCode::Char('>') | KeyCode::PageDown => app.view_date = app.view_date + Months::new(1),
+        KeyCode::Enter | KeyCode::Char('l') => {
+            app.view = View::Day;
+            app.view_selected = None;
+        }
+        KeyCode::Char('g') => {
+            // First class on or after the day, the table only holds what is still ahead.
+            let index = shown.iter().position(|course| {
+                parse_course_datetime(course).is_some_and(|(start, _)| start.date() >= app.view_date)
+            });
+            if let Some(index) = index {
+                app.selected = Some(index);
+                app.last_selected = Some(index);
+                app.scroll_offset = index;
+            }
+            app.view = View::Table;
+        }
+        KeyCode::Char('m') | KeyCode::Backspace | KeyCode::Char('h') => {
+            app.view = View::Table;
+        }
+        _ => return false,
+    }
+    true
+}
@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cache::{cache_dir, cache_path};
+use crate::timetable::{session_key, CourseAction};
+
+/// The user's own notes, the API `note` field is left alone.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Notes {
+    /// Keyed `DEPT/ABBR`, shown with every session of the course.
+    pub courses: HashMap<String, String>,
+    /// Keyed by action id and date, see `session_key`.
+    pub sessions: HashMap<String, String>,
+}
+
+#[derive(Clone)]
+pub enum NoteTarget {
+    Course(String),
+    Session(String),
+}
+
+pub fn course_key(course: &CourseAction) -> String {
+    format!(
+        "{}/{}",
+        course.dept.as_deref().unwrap_or(""),
+        course.abbr.as_deref().unwrap_or("")
+    )
+}
+
+impl Notes {
+    pub fn load() -> Self {
+        fs::read_to_string(cache_path("notes.json"))
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if fs::create_dir_all(cache_dir()).is_err() {
+            return;
+        }
+        if let Ok(data) = serde_json::to_string_pretty(self) {
+            let _ = fs::write(cache_path("notes.json"), data);
+        }
+    }
+
+    pub fn course_note(&self, course: &CourseAction) -> Option<&str> {
+        self.courses.get(&course_key(course)).map(String::as_str)
+    }
+
+    pub fn session_note(&self, course: &CourseAction) -> Option<&str> {
+        session_key(course).and_then(|key| self.sessions.get(&key)).map(String::as_str)
+    }
+
+    /// Both notes of a class, course note first.
+    pub fn text(&self, course: &CourseAction) -> Option<String> {
+        match (self.course_note(course), self.session_note(course)) {
+            (Some(course_note), Some(session_note)) => Some(format!("{}\n{}", course_note, session_note)),
+            (course_note, session_note) => course_note.or(session_note).map(str::to_string),
+        }
+    }
+
+    pub fn get(&self, target: &NoteTarget) -> Option<&str> {
+        match target {
+            NoteTarget::Course(key) => self.courses.get(key),
+            NoteTarget::Session(key) => self.sessions.get(key),
+        }
+        .map(String::as_str)
+    }
+
+    /// Stores a note, an empty one removes it.
+    pub fn set(&mut self, target: &NoteTarget, text: &str) {
+        let (notes, key) = match target {
+            NoteTarget::Course(key) => (&mut self.courses, key),
+            NoteTarget::Session(key) => (&mut self.sessions, key),
+        };
+        let text = text.trim();
+        if text.is_empty() {
+            notes.remove(key);
+        } else {
+            notes.insert(key.clone(), text.to_string());
+        }
+    }
+}
+
+/// A note being written in the TUI.
+pub struct NoteEditor {
+    pub target: NoteTarget,
+    pub title: String,
+    pub text: String,
+}
+
+impl NoteEditor {
+    pub fn new(notes: &Notes, target: NoteTarget, title: String) -> Self {
+        let text = notes.get(&target).unwrap_or_default().to_string();
+        NoteEditor { target, title, text }
+    }
+}
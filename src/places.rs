@@ -0,0 +1,131 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use crate::cache::strip_diacritics;
+use crate::config::config_dir;
+
+/// Buildings shipped with the program, `places.json` in the config directory adds to them.
+const BUILT_IN: &str = include_str!("../data/places.json");
+
+#[derive(Deserialize, Default, Clone)]
+#[serde(default)]
+pub struct Place {
+    pub name: Option<String>,
+    pub address: Option<String>,
+    pub note: Option<String>,
+    pub rooms: BTreeMap<String, Room>,
+}
+
+#[derive(Deserialize, Default, Clone)]
+#[serde(default)]
+pub struct Room {
+    pub floor: Option<String>,
+    pub description: Option<String>,
+}
+
+impl Room {
+    /// E.g. "floor 6, computer lab".
+    pub fn text(&self) -> String {
+        let floor = self.floor.as_ref().map(|floor| format!("floor {}", floor));
+        [floor, self.description.clone()].into_iter().flatten().collect::<Vec<_>>().join(", ")
+    }
+}
+
+/// Place codes as used in the timetable, e.g. "CP", mapped to what they mean.
+#[derive(Default)]
+pub struct Directory {
+    pub places: BTreeMap<String, Place>,
+}
+
+pub fn places_path() -> PathBuf {
+    config_dir().join("places.json")
+}
+
+fn parse(data: &str) -> Result<BTreeMap<String, Place>, serde_json::Error> {
+    serde_json::from_str(data)
+}
+
+impl Directory {
+    /// The built-in directory with the user's file laid over it: given fields
+    /// replace the shipped ones and rooms are added per place.
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let mut places = parse(BUILT_IN)?;
+        let path = places_path();
+        if let Ok(data) = fs::read_to_string(&path) {
+            let own = parse(&data).map_err(|e| format!("Invalid place directory {}: {}", path.display(), e))?;
+            for (code, place) in own {
+                let entry = places.entry(code).or_default();
+                entry.name = place.name.or(entry.name.take());
+                entry.address = place.address.or(entry.address.take());
+                entry.note = place.note.or(entry.note.take());
+                entry.rooms.extend(place.rooms);
+            }
+        }
+        Ok(Directory { places })
+    }
+
+    pub fn room(&self, place: &str, room: &str) -> Option<&Room> {
+        self.places.get(place)?.rooms.get(room)
+    }
+
+    /// What the directory knows about a place and room, one line each.
+    pub fn describe(&self, place: &str, room: &str) -> Vec<String> {
+        let mut lines = Vec::new();
+        if let Some(entry) = self.places.get(place) {
+            let mut building = format!("Building {}", place);
+            if let Some(name) = &entry.name {
+                building.push_str(&format!(": {}", name));
+            }
+            if let Some(address) = &entry.address {
+                building.push_str(&format!(", {}", address));
+            }
+            lines.push(building);
+            if let Some(note) = &entry.note {
+                lines.push(format!("  {}", note));
+            }
+        }
+        if let Some(entry) = self.room(place, room).filter(|entry| !entry.text().is_empty()) {
+            lines.push(format!("Room {}: {}", room, entry.text()));
+        }
+        lines
+    }
+
+    /// Places and rooms whose code, name, address or description contains `query`,
+    /// regardless of case and diacritics.
+    pub fn search(&self, query: &str) -> Vec<(&String, &Place)> {
+        // "usti" finds "Ústí", the texts themselves keep their diacritics.
+        let fold = |text: &str| strip_diacritics(text).to_lowercase();
+        let query = fold(query);
+        let contains = |text: &str| fold(text).contains(&query);
+        let matches = |text: &Option<String>| text.as_deref().is_some_and(contains);
+        self.places
+            .iter()
+            .filter(|(code, place)| {
+                contains(code)
+                    || matches(&place.name)
+                    || matches(&place.address)
+                    || matches(&place.note)
+                    || place.rooms.iter().any(|(room, entry)| contains(room) || matches(&entry.description))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_ignores_diacritics_but_keeps_them_for_display() {
+        let places = parse(r#"{"CP": {"address": "Pasteurova 3632/15, 400 96 Ústí nad Labem"}}"#).unwrap();
+        let directory = Directory { places };
+        let found = directory.search("usti");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].1.address.as_deref(), Some("Pasteurova 3632/15, 400 96 Ústí nad Labem"));
+        assert_eq!(directory.search("ÚSTÍ").len(), 1);
+        assert!(directory.search("brno").is_empty());
+    }
+}
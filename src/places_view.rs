@@ -0,0 +1,100 @@
+use std::collections::BTreeSet;
+
+use crossterm::event::KeyCode;
+use ratatui::{
+    backend::Backend,
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Clear, Row, Table, TableState},
+};
+
+use crate::app::{App, View};
+use crate::courses_view::highlighted;
+use crate::theme::theme;
+
+/// Code, name, address or floor, note or description, and whether it is a building row.
+type PlaceRow = (String, String, String, String, bool);
+
+/// Directory entries matching the search, each building followed by its rooms,
+/// then the timetable's places the directory does not know yet.
+fn place_rows(app: &App) -> Vec<PlaceRow> {
+    let query = app.search_query.as_deref().unwrap_or("");
+    let mut rows = Vec::new();
+    for (code, place) in app.places.search(query) {
+        rows.push((
+            code.clone(),
+            place.name.clone().unwrap_or_default(),
+            place.address.clone().unwrap_or_default(),
+            place.note.clone().unwrap_or_default(),
+            true,
+        ));
+        for (room, entry) in &place.rooms {
+            rows.push((
+                format!("  {}", room),
+                String::new(),
+                entry.floor.as_ref().map_or(String::new(), |floor| format!("floor {}", floor)),
+                entry.description.clone().unwrap_or_default(),
+                false,
+            ));
+        }
+    }
+    let unknown: BTreeSet<&str> = app
+        .courses
+        .iter()
+        .filter_map(|course| course.place.as_deref())
+        .filter(|place| !place.is_empty() && !app.places.places.contains_key(*place))
+        .filter(|place| place.to_lowercase().contains(&query.to_lowercase()))
+        .collect();
+    for place in unknown {
+        rows.push((place.to_string(), "not in the directory".to_string(), String::new(), String::new(), true));
+    }
+    rows
+}
+
+pub fn draw_places_view<B: Backend>(f: &mut ratatui::Frame<B>, area: Rect, app: &App) {
+    let header_style = theme().header;
+    let rows: Vec<Row> = place_rows(app)
+        .into_iter()
+        .map(|(code, name, address, note, building)| {
+            let style = if building { Style::default().add_modifier(Modifier::BOLD) } else { Style::default() };
+            Row::new(vec![code, name, address, note]).style(style)
+        })
+        .collect();
+
+    let title = match app.search_query.as_deref().filter(|query| !query.is_empty()) {
+        Some(query) => format!("Buildings and rooms matching '{}' ([/] search)", query),
+        None => "Buildings and rooms ([/] search, add your own in places.json)".to_string(),
+    };
+    let table = Table::new(rows)
+        .header(Row::new(vec!["Code", "Name", "Address / floor", "Note"]).style(header_style))
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .widths(&[
+            Constraint::Length(8),
+            Constraint::Percentage(25),
+            Constraint::Percentage(30),
+            Constraint::Percentage(35),
+        ]);
+
+    let mut state = TableState::default();
+    state.select(app.view_selected);
+    f.render_widget(Clear, area);
+    f.render_stateful_widget(highlighted(table), area, &mut state);
+}
+
+/// Keys of the places view. Returns false for keys left to the main key handling.
+pub fn handle_places_key(app: &mut App, code: KeyCode) -> bool {
+    let count = place_rows(app).len();
+    match code {
+        KeyCode::Down | KeyCode::Char('j') if count > 0 => {
+            app.view_selected = Some(app.view_selected.map_or(0, |i| (i + 1).min(count - 1)));
+        }
+        KeyCode::Up | KeyCode::Char('k') if count > 0 => {
+            app.view_selected = Some(app.view_selected.map_or(0, |i| i.saturating_sub(1)));
+        }
+        KeyCode::Char('p') | KeyCode::Backspace | KeyCode::Char('h') => {
+            app.view = View::Table;
+        }
+        _ => return false,
+    }
+    true
+}
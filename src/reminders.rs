@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use std::fs;
+use std::process::{Command, Stdio};
+
+use chrono::{Duration, NaiveDateTime};
+
+use crate::cache::{cache_dir, cache_path};
+use crate::config::ReminderConfig;
+use crate::timetable::{parse_course_datetime, session_key, CourseAction};
+
+/// A reminder that is due now.
+#[derive(Clone, Copy)]
+pub struct Reminder<'a> {
+    pub course: &'a CourseAction,
+    pub start: NaiveDateTime,
+    pub offset: i64,
+}
+
+impl Reminder<'_> {
+    /// Identifies a reminder across syncs and restarts: the session and the offset.
+    /// Sessions without an id go by start time and abbreviation instead.
+    fn key(&self) -> String {
+        let session = session_key(self.course).unwrap_or_else(|| {
+            format!(
+                "{}-{}",
+                self.start.format("%Y%m%d%H%M"),
+                self.course.abbr.as_deref().unwrap_or("N/A")
+            )
+        });
+        format!("{}-{}", session, self.offset)
+    }
+
+    pub fn minutes_left(&self, now: NaiveDateTime) -> i64 {
+        (self.start - now).num_minutes().max(0)
+    }
+
+    pub fn message(&self, now: NaiveDateTime) -> String {
+        format!(
+            "{} starts in {} minutes in {} {}",
+            self.course.abbr.as_deref().or(self.course.name.as_deref()).unwrap_or("N/A"),
+            self.minutes_left(now),
+            self.course.place.as_deref().unwrap_or("N/A"),
+            self.course.room.as_deref().unwrap_or("N/A"),
+        )
+    }
+
+    fn fields(&self, now: NaiveDateTime) -> Vec<(&'static str, String)> {
+        let course = self.course;
+        vec![
+            ("id", course.id.map_or(String::new(), |id| id.to_string())),
+            ("name", course.name.clone().unwrap_or_default()),
+            ("abbr", course.abbr.clone().unwrap_or_default()),
+            ("dept", course.dept.clone().unwrap_or_default()),
+            ("type", course.class_type.clone().unwrap_or_default()),
+            ("place", course.place.clone().unwrap_or_default()),
+            ("room", course.room.clone().unwrap_or_default()),
+            ("date", course.date.clone().unwrap_or_default()),
+            ("from", course.timeFrom.clone().unwrap_or_default()),
+            ("to", course.timeTo.clone().unwrap_or_default()),
+            ("minutes", self.minutes_left(now).to_string()),
+            ("message", self.message(now)),
+        ]
+    }
+
+    /// Runs the hook through `sh -c`. Placeholders are shell-quoted, the same values
+    /// are exported as `UJEP_NAME`, `UJEP_ROOM`, ... for scripts.
+    pub fn run(&self, command: &str, now: NaiveDateTime) -> std::io::Result<()> {
+        let fields = self.fields(now);
+        let mut line = command.to_string();
+        for (key, value) in &fields {
+            line = line.replace(&format!("{{{}}}", key), &shell_quote(value));
+        }
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c")
+            .arg(line)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        for (key, value) in &fields {
+            cmd.env(format!("UJEP_{}", key.to_uppercase()), value);
+        }
+        let mut child = cmd.spawn()?;
+        std::thread::spawn(move || child.wait());
+        Ok(())
+    }
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+fn load_sent() -> HashMap<String, NaiveDateTime> {
+    fs::read_to_string(cache_path("reminders_sent.json"))
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+/// Keeps track of fired reminders in the cache so nothing fires twice,
+/// not after a sync and not after a restart.
+pub struct Reminders {
+    pub config: ReminderConfig,
+    sent: HashMap<String, NaiveDateTime>,
+}
+
+impl Reminders {
+    pub fn new(config: ReminderConfig) -> Self {
+        Reminders { config, sent: load_sent() }
+    }
+
+    /// Reminders whose offset has passed while the class has not started yet.
+    pub fn due<'a>(&self, courses: &[&'a CourseAction], now: NaiveDateTime) -> Vec<Reminder<'a>> {
+        let mut due = Vec::new();
+        for &course in courses {
+            let Some((start, _)) = parse_course_datetime(course) else {
+                continue;
+            };
+            if start <= now {
+                continue;
+            }
+            // Only the closest passed offset fires, so a late start does not
+            // fire "60 minutes" and "10 minutes" at once.
+            let offset = self
+                .config
+                .offsets
+                .iter()
+                .filter(|&&offset| now >= start - Duration::minutes(offset))
+                .min();
+            if let Some(&offset) = offset {
+                let reminder = Reminder { course, start, offset };
+                if !self.sent.contains_key(&reminder.key()) {
+                    due.push(reminder);
+                }
+            }
+        }
+        due
+    }
+
+    /// Fires all due reminders and returns their messages.
+    pub fn tick(&mut self, courses: &[&CourseAction], now: NaiveDateTime) -> Vec<String> {
+        let Some(command) = self.config.command.clone() else {
+            return Vec::new();
+        };
+        if self.due(courses, now).is_empty() {
+            return Vec::new();
+        }
+        // The TUI and `remind` may run side by side, whatever the other one
+        // fired in the meantime is in the file.
+        self.sent.extend(load_sent());
+        let due = self.due(courses, now);
+        let mut messages = Vec::new();
+        for reminder in due {
+            // A broken hook should not bring down the TUI or the daemon.
+            let _ = reminder.run(&command, now);
+            messages.push(reminder.message(now));
+            for &offset in &self.config.offsets {
+                if offset >= reminder.offset {
+                    let key = Reminder { offset, ..reminder }.key();
+                    self.sent.insert(key, reminder.start);
+                }
+            }
+        }
+        self.save(now);
+        messages
+    }
+
+    fn save(&mut self, now: NaiveDateTime) {
+        self.sent.retain(|_, start| *start > now - Duration::days(1));
+        if fs::create_dir_all(cache_dir()).is_err() {
+            return;
+        }
+        if let Ok(data) = serde_json::to_string(&self.sent) {
+            let _ = fs::write(cache_path("reminders_sent.json"), data);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timetable::test_course;
+
+    fn key(course: &CourseAction) -> String {
+        let (start, _) = parse_course_datetime(course).unwrap();
+        Reminder { course, start, offset: 15 }.key()
+    }
+
+    #[test]
+    fn sessions_without_an_id_get_their_own_keys() {
+        let with_id = test_course(7, "21.10.2026", "09:00", "10:30");
+        assert_eq!(key(&with_id), "7-20261021-15");
+
+        let mut first = test_course(1, "21.10.2026", "09:00", "10:30");
+        first.id = None;
+        let mut later = test_course(2, "21.10.2026", "13:00", "14:30");
+        later.id = None;
+        let mut other = test_course(3, "21.10.2026", "09:00", "10:30");
+        other.id = None;
+        assert_ne!(key(&first), key(&later));
+        assert_ne!(key(&first), key(&other));
+    }
+}
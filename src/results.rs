@@ -0,0 +1,201 @@
+use std::fs;
+
+use chrono::{Local, NaiveDate};
+use serde_json::Value;
+
+use crate::cache::{cache_dir, cache_path, strip_diacritics};
+use crate::timetable::CourseAction;
+
+/// The outcome of one enrolled course, as STAG lists it for the student.
+pub struct StudyResult {
+    pub dept: String,
+    pub abbr: String,
+    pub name: String,
+    pub year: String,
+    pub semester: String,
+    pub credits: Option<u32>,
+    /// How the course is finished, e.g. "Zkouska" or "Zapocet".
+    pub kind: String,
+    pub grade: Option<String>,
+    pub date: Option<NaiveDate>,
+    pub attempt: Option<u32>,
+    pub passed: bool,
+    /// Stand-in for the details popup, which reads the cached course details.
+    pub course: CourseAction,
+}
+
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.trim().to_string(),
+        Value::Number(n) => n.to_string(),
+        _ => String::new(),
+    }
+}
+
+fn non_empty(value: &Value) -> Option<String> {
+    Some(text(value)).filter(|s| !s.is_empty())
+}
+
+fn date(value: &Value) -> Option<NaiveDate> {
+    let date = value["value"].as_str().or_else(|| value.as_str())?;
+    NaiveDate::parse_from_str(date, "%d.%m.%Y").ok()
+}
+
+/// Grade as a number for the average, the ECTS letters count in halves.
+pub fn grade_value(grade: &str) -> Option<f64> {
+    match grade {
+        "1" | "A" => Some(1.0),
+        "B" => Some(1.5),
+        "2" | "C" => Some(2.0),
+        "D" => Some(2.5),
+        "3" | "E" => Some(3.0),
+        "4" | "F" => Some(4.0),
+        _ => None,
+    }
+}
+
+/// Credits from the cached course details, for results that do not list them.
+fn cached_credits(dept: &str, abbr: &str, year: &str) -> Option<u32> {
+    let path = cache_path("course_details").join(format!("{}_{}_{}.json", dept, abbr, year));
+    let details: Value = serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+    text(&details["kreditu"]).parse().ok()
+}
+
+impl StudyResult {
+    /// Parses one `student_na_predmetu` entry.
+    pub fn from_stag(entry: &Value) -> Option<Self> {
+        let dept = non_empty(&entry["katedra"])?;
+        let abbr = non_empty(&entry["zkratka"])?;
+        let year = text(&entry["rok"]);
+        let semester = text(&entry["semestr"]);
+        let name = non_empty(&entry["nazev"]).unwrap_or_else(|| abbr.clone());
+        let credits = text(&entry["kreditu"]).parse().ok().or_else(|| cached_credits(&dept, &abbr, &year));
+
+        // Courses finished by an exam carry its grade, the others only the credit ("S" = passed).
+        let exam_grade = non_empty(&entry["zk_hodnoceni"]);
+        let credit = non_empty(&entry["zapocet"]);
+        let (kind, grade, date, passed) = match (&exam_grade, non_empty(&entry["zk_typ_hodnoceni"])) {
+            (Some(grade), kind) => (
+                kind.unwrap_or_else(|| "Zkouska".to_string()),
+                Some(grade.clone()),
+                date(&entry["zk_datum"]),
+                grade_value(grade).is_some_and(|value| value < 4.0) || grade == "S",
+            ),
+            (None, Some(kind)) => (kind, None, date(&entry["zk_datum"]), false),
+            (None, None) => (
+                "Zapocet".to_string(),
+                credit.clone().filter(|credit| credit == "S"),
+                date(&entry["zap_datum"]),
+                credit.as_deref() == Some("S"),
+            ),
+        };
+
+        let course = CourseAction {
+            id: None,
+            name: Some(name.clone()),
+            dept: Some(dept.clone()),
+            abbr: Some(abbr.clone()),
+            year: Some(year.clone()),
+            semester: Some(semester.clone()),
+            date: None,
+            timeFrom: None,
+            timeTo: None,
+            place: None,
+            room: None,
+            class_type: None,
+            day: None,
+            weekType: None,
+            weekFrom: None,
+            weekTo: None,
+            note: None,
+            contact: None,
+            statut: None,
+            teachingTeacherStagId: None,
+        };
+        Some(StudyResult {
+            dept,
+            abbr,
+            name,
+            year,
+            semester,
+            credits,
+            kind,
+            grade,
+            date,
+            attempt: text(&entry["zk_pokus"]).parse().ok(),
+            passed,
+            course,
+        })
+    }
+
+    pub fn status(&self) -> &'static str {
+        if self.passed {
+            "passed"
+        } else if self.grade.is_some() {
+            "failed"
+        } else {
+            "pending"
+        }
+    }
+}
+
+/// Earned against enrolled credits and the credit-weighted grade average.
+pub struct ResultsSummary {
+    pub enrolled: u32,
+    pub earned: u32,
+    pub average: Option<f64>,
+}
+
+impl ResultsSummary {
+    pub fn compute<'r>(results: impl IntoIterator<Item = &'r StudyResult>) -> Self {
+        let mut summary = ResultsSummary { enrolled: 0, earned: 0, average: None };
+        let (mut weighted, mut weights) = (0.0, 0.0);
+        for result in results {
+            let credits = result.credits.unwrap_or(0);
+            summary.enrolled += credits;
+            if result.passed {
+                summary.earned += credits;
+            }
+            // Failed exams count with a 4, like in STAG.
+            if let Some(value) = result.grade.as_deref().and_then(grade_value) {
+                weighted += value * credits as f64;
+                weights += credits as f64;
+            }
+        }
+        if weights > 0.0 {
+            summary.average = Some(weighted / weights);
+        }
+        summary
+    }
+
+    pub fn text(&self) -> String {
+        let average = self.average.map_or("-".to_string(), |average| format!("{:.2}", average));
+        format!("{}/{} credits, average {}", self.earned, self.enrolled, average)
+    }
+}
+
+pub fn save_results(response: &Value) -> Result<(), Box<dyn std::error::Error>> {
+    let mut response = response.clone();
+    response["retrieved_at"] = Value::String(Local::now().naive_local().to_string());
+    fs::create_dir_all(cache_dir())?;
+    fs::write(cache_path("results.json"), serde_json::to_string_pretty(&response)?)?;
+    Ok(())
+}
+
+/// Cached results, newest year first, empty before the first successful sync.
+pub fn load_results() -> Vec<StudyResult> {
+    let response: Value = fs::read_to_string(cache_path("results.json"))
+        .ok()
+        .and_then(|data| serde_json::from_str(&strip_diacritics(&data)).ok())
+        .unwrap_or_default();
+    let mut results: Vec<StudyResult> = response["student_na_predmetu"]
+        .as_array()
+        .map(|entries| entries.iter().filter_map(StudyResult::from_stag).collect())
+        .unwrap_or_default();
+    // The winter semester opens the academic year.
+    let semester_order = |result: &StudyResult| if result.semester == "ZS" { 0 } else { 1 };
+    results.sort_by(|a, b| {
+        (&b.year, semester_order(b), &a.abbr).cmp(&(&a.year, semester_order(a), &b.abbr))
+    });
+    results
+}
@@ -0,0 +1,102 @@
+use crossterm::event::KeyCode;
+use ratatui::{
+    backend::Backend,
+    layout::{Constraint, Rect},
+    style::Style,
+    widgets::{Block, Borders, Clear, Row, Table, TableState},
+};
+
+use crate::app::{App, View};
+use crate::courses_view::highlighted;
+use crate::results::{ResultsSummary, StudyResult};
+use crate::theme::theme;
+
+fn result_style(result: &StudyResult) -> Style {
+    match result.status() {
+        "passed" => theme().good,
+        "failed" => theme().bad,
+        _ => Style::default(),
+    }
+}
+
+pub fn draw_results_view<B: Backend>(f: &mut ratatui::Frame<B>, area: Rect, app: &App) {
+    let results = app.results;
+    let header_style = theme().header;
+
+    let rows: Vec<Row> = results
+        .iter()
+        .map(|result| {
+            Row::new(vec![
+                result.year.clone(),
+                result.semester.clone(),
+                format!("{}/{}", result.dept, result.abbr),
+                result.name.clone(),
+                result.credits.map_or(String::new(), |credits| credits.to_string()),
+                result.kind.clone(),
+                result.grade.clone().unwrap_or_default(),
+                result.date.map_or(String::new(), |date| date.format("%-d.%-m.%Y").to_string()),
+                result.attempt.map_or(String::new(), |attempt| attempt.to_string()),
+                result.status().to_string(),
+            ])
+            .style(result_style(result))
+        })
+        .collect();
+
+    let title = match results.first() {
+        None => "Results - none cached, [s]ync to fetch them".to_string(),
+        Some(newest) => format!(
+            "Results: {} | {}: {} ([Enter] details)",
+            ResultsSummary::compute(results).text(),
+            newest.year,
+            ResultsSummary::compute(results.iter().filter(|r| r.year == newest.year)).text()
+        ),
+    };
+    let table = Table::new(rows)
+        .header(
+            Row::new(vec!["Year", "Sem", "Course", "Name", "Cr", "Type", "Grade", "Date", "Try", "Status"])
+                .style(header_style),
+        )
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .widths(&[
+            Constraint::Length(5),
+            Constraint::Length(3),
+            Constraint::Length(10),
+            Constraint::Percentage(30),
+            Constraint::Length(3),
+            Constraint::Length(12),
+            Constraint::Length(5),
+            Constraint::Length(10),
+            Constraint::Length(3),
+            Constraint::Length(7),
+        ]);
+
+    let mut state = TableState::default();
+    state.select(app.view_selected);
+    f.render_widget(Clear, area);
+    f.render_stateful_widget(highlighted(table), area, &mut state);
+}
+
+/// Keys of the results view. Returns false for keys left to the main key handling.
+pub fn handle_results_key(app: &mut App, code: KeyCode) -> bool {
+    let results = app.results;
+    let count = results.len();
+    match code {
+        KeyCode::Down | KeyCode::Char('j') if count > 0 => {
+            app.view_selected = Some(app.view_selected.map_or(0, |i| (i + 1).min(count - 1)));
+        }
+        KeyCode::Up | KeyCode::Char('k') if count > 0 => {
+            app.view_selected = Some(app.view_selected.map_or(0, |i| i.saturating_sub(1)));
+        }
+        KeyCode::Enter | KeyCode::Char('l') => {
+            if let Some(result) = app.view_selected.and_then(|i| results.get(i)) {
+                app.details_course = Some(&result.course);
+                app.show_details = true;
+            }
+        }
+        KeyCode::Char('r') | KeyCode::Backspace | KeyCode::Char('h') => {
+            app.view = View::Table;
+        }
+        _ => return false,
+    }
+    true
+}
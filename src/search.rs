@@ -0,0 +1,117 @@
+use crate::timetable::CourseAction;
+
+struct LoweredFields {
+    class_type: String,
+    name: String,
+    dept: String,
+    abbr: String,
+    year: String,
+    semester: String,
+    date: String,
+    time_from: String,
+    time_to: String,
+    place: String,
+    room: String,
+    day: String,
+    week_type: String,
+    week_from: String,
+    week_to: String,
+    note: String,
+    user_note: String,
+    contact: String,
+    statut: String,
+    teacher_id: String,
+}
+
+impl LoweredFields {
+    fn new(course: &CourseAction, user_note: Option<&str>) -> Self {
+        Self {
+            class_type: course.class_type.as_deref().unwrap_or("").to_lowercase(),
+            name: course.name.as_deref().unwrap_or("").to_lowercase(),
+            dept: course.dept.as_deref().unwrap_or("").to_lowercase(),
+            abbr: course.abbr.as_deref().unwrap_or("").to_lowercase(),
+            year: course.year.as_deref().unwrap_or("").to_lowercase(),
+            semester: course.semester.as_deref().unwrap_or("").to_lowercase(),
+            date: course.date.as_deref().unwrap_or("").to_lowercase(),
+            time_from: course.timeFrom.as_deref().unwrap_or("").to_lowercase(),
+            time_to: course.timeTo.as_deref().unwrap_or("").to_lowercase(),
+            place: course.place.as_deref().unwrap_or("").to_lowercase(),
+            room: course.room.as_deref().unwrap_or("").to_lowercase(),
+            day: course.day.as_deref().unwrap_or("").to_lowercase(),
+            week_type: course.weekType.as_deref().unwrap_or("").to_lowercase(),
+            week_from: course.weekFrom.map_or(String::new(), |v| v.to_string()),
+            week_to: course.weekTo.map_or(String::new(), |v| v.to_string()),
+            note: course.note.as_deref().unwrap_or("").to_lowercase(),
+            user_note: user_note.unwrap_or("").to_lowercase(),
+            contact: course.contact.as_deref().unwrap_or("").to_lowercase(),
+            statut: course.statut.as_deref().unwrap_or("").to_lowercase(),
+            teacher_id: course.teachingTeacherStagId.map_or(String::new(), |v| v.to_string()),
+        }
+    }
+
+    
+    fn contains_any(&self, q: &str) -> bool {
+        self.name.contains(q)
+            || self.dept.contains(q)
+            || self.abbr.contains(q)
+            || self.year.contains(q)
+            || self.semester.contains(q)
+            || self.date.contains(q)
+            || self.time_from.contains(q)
+            || self.time_to.contains(q)
+            || self.place.contains(q)
+            || self.room.contains(q)
+            || self.class_type.contains(q)
+            || self.day.contains(q)
+            || self.week_type.contains(q)
+            || self.week_from.contains(q)
+            || self.week_to.contains(q)
+            || self.note.contains(q)
+            || self.user_note.contains(q)
+            || self.contact.contains(q)
+            || self.statut.contains(q)
+            || self.teacher_id.contains(q)
+    }
+}
+
+/// Matches a course against a search query, either plain text over all fields
+/// or `field:text` restricted to one field (e.g. `day:po`). `note:` covers the
+/// user's own notes as well.
+pub fn matches_query(course: &CourseAction, user_note: Option<&str>, query: &str) -> bool {
+    if query.is_empty() {
+        return true;
+    }
+    let q_lower = query.to_lowercase();
+    let mut parts = q_lower.splitn(2, ':');
+    let (field, q_part) = if let (Some(field), Some(q_part)) = (parts.next(), parts.next()) {
+        (field, q_part)
+    } else {
+        ("", q_lower.as_str())
+    };
+
+    let lf = LoweredFields::new(course, user_note);
+
+    match field {
+        "type" => lf.class_type.contains(q_part),
+        "name" => lf.name.contains(q_part),
+        "dept" => lf.dept.contains(q_part),
+        "abbr" => lf.abbr.contains(q_part),
+        "year" => lf.year.contains(q_part),
+        "semester" => lf.semester.contains(q_part),
+        "date" => lf.date.contains(q_part),
+        "timefrom" => lf.time_from.contains(q_part),
+        "timeto" => lf.time_to.contains(q_part),
+        "place" => lf.place.contains(q_part),
+        "room" => lf.room.contains(q_part),
+        "day" => lf.day.contains(q_part),
+        "weektype" => lf.week_type.contains(q_part),
+        "weekfrom" => lf.week_from.contains(q_part),
+        "weekto" => lf.week_to.contains(q_part),
+        "note" => lf.note.contains(q_part) || lf.user_note.contains(q_part),
+        "contact" => lf.contact.contains(q_part),
+        "statut" => lf.statut.contains(q_part),
+        "teacherid" => lf.teacher_id.contains(q_part),
+
+        _ => lf.contains_any(q_part),
+    }
+}
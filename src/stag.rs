@@ -0,0 +1,122 @@
+use reqwest::header::HeaderMap;
+use serde_json::Value;
+
+/// Talks to the STAG endpoints behind ujepice. The base URL is injectable so a
+/// local mock server can stand in for `ujepice.ujep.cz`.
+pub struct StagClient {
+    client: reqwest::Client,
+    base_url: String,
+    headers: HeaderMap,
+}
+
+impl StagClient {
+    pub fn new(client: reqwest::Client, base_url: &str, headers: HeaderMap) -> Self {
+        StagClient { client, base_url: base_url.trim_end_matches('/').to_string(), headers }
+    }
+
+    /// GETs `path` and checks that the answer holds a `key` array, an expired
+    /// token or an unknown student answers with an error body instead.
+    async fn get_list(&self, path: &str, key: &str) -> Result<Value, Box<dyn std::error::Error>> {
+        let response = self
+            .client
+            .get(format!("{}{}", self.base_url, path))
+            .headers(self.headers.clone())
+            .send()
+            .await?
+            .json::<Value>()
+            .await?;
+        if !response[key].is_array() {
+            return Err(format!("Request for {} was rejected", key).into());
+        }
+        Ok(response)
+    }
+
+    /// Registered and open exam terms of the student, the raw STAG response.
+    pub async fn fetch_exam_terms(&self, stagid: &str) -> Result<Value, Box<dyn std::error::Error>> {
+        let path = format!("/api/stag/exams/get-exam-terms-for-student?osCislo={}&outputFormat=JSON", stagid);
+        self.get_list(&path, "termin").await
+    }
+
+    /// Results of every course the student enrolled in, all years.
+    pub async fn fetch_results(&self, stagid: &str) -> Result<Value, Box<dyn std::error::Error>> {
+        let path = format!("/api/stag/grades/get-grades-by-student?osCislo={}&outputFormat=JSON", stagid);
+        self.get_list(&path, "student_na_predmetu").await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::{HeaderValue, AUTHORIZATION};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio::sync::oneshot;
+
+    /// Answers a single request with `body` and hands back the request head.
+    async fn mock_server(body: &'static str) -> (String, oneshot::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let (sender, receiver) = oneshot::channel();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let read = stream.read(&mut buffer).await.unwrap();
+                if read == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buffer[..read]);
+            }
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+            let _ = sender.send(String::from_utf8_lossy(&request).into_owned());
+        });
+        (url, receiver)
+    }
+
+    fn client(url: &str) -> StagClient {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer token"));
+        StagClient::new(reqwest::Client::new(), url, headers)
+    }
+
+    #[tokio::test]
+    async fn fetches_exam_terms() {
+        let (url, request) = mock_server(r#"{"termin": [{"termIdno": 1}]}"#).await;
+        let terms = client(&url).fetch_exam_terms("F21000").await.unwrap();
+        assert_eq!(terms["termin"][0]["termIdno"], 1);
+
+        let request = request.await.unwrap().to_lowercase();
+        assert!(request.starts_with("get /api/stag/exams/get-exam-terms-for-student?oscislo=f21000&outputformat=json "));
+        assert!(request.contains("authorization: bearer token"));
+    }
+
+    #[tokio::test]
+    async fn rejects_exam_terms_without_terms() {
+        let (url, _) = mock_server(r#"{"error": "token expired"}"#).await;
+        let error = client(&url).fetch_exam_terms("F21000").await.unwrap_err();
+        assert_eq!(error.to_string(), "Request for termin was rejected");
+    }
+
+    #[tokio::test]
+    async fn fetches_results() {
+        let (url, request) = mock_server(r#"{"student_na_predmetu": [{"zkratka": "OPS"}]}"#).await;
+        let results = client(&url).fetch_results("F21000").await.unwrap();
+        assert_eq!(results["student_na_predmetu"][0]["zkratka"], "OPS");
+
+        let request = request.await.unwrap().to_lowercase();
+        assert!(request.starts_with("get /api/stag/grades/get-grades-by-student?oscislo=f21000&outputformat=json "));
+    }
+
+    #[tokio::test]
+    async fn rejects_results_without_courses() {
+        let (url, _) = mock_server(r#"{"student_na_predmetu": null}"#).await;
+        let error = client(&url).fetch_results("F21000").await.unwrap_err();
+        assert_eq!(error.to_string(), "Request for student_na_predmetu was rejected");
+    }
+}
@@ -0,0 +1,210 @@
+use chrono::{Duration, NaiveDateTime};
+use serde_json::json;
+
+use crate::timetable::{is_course_ongoing, parse_course_datetime, CourseAction};
+
+/// What is happening right now, computed from the sorted, non-ignored courses.
+pub enum ClassStatus<'a> {
+    /// A class is running, `progress` is the elapsed part of it.
+    Ongoing {
+        course: &'a CourseAction,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+        progress: f64,
+    },
+    /// Waiting for the next class, `progress` is the elapsed part of the free time
+    /// since the previous class ended (0 if nothing ended yet).
+    Next {
+        course: &'a CourseAction,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+        progress: f64,
+    },
+    None,
+}
+
+impl<'a> ClassStatus<'a> {
+    pub fn compute(displayed: &[&'a CourseAction], now: NaiveDateTime) -> Self {
+        if let Some(&course) = displayed.iter().find(|&&c| is_course_ongoing(c, now)) {
+            let (start, end) = parse_course_datetime(course).unwrap();
+            let total = (end - start).num_seconds().max(1) as f64;
+            let elapsed = (now - start).num_seconds().max(0) as f64;
+            return ClassStatus::Ongoing {
+                course,
+                start,
+                end,
+                progress: (elapsed / total).min(1.0),
+            };
+        }
+
+        let next = displayed.iter().find_map(|&c| {
+            parse_course_datetime(c)
+                .filter(|(start, _)| *start > now)
+                .map(|(start, end)| (c, start, end))
+        });
+        let Some((course, start, end)) = next else {
+            return ClassStatus::None;
+        };
+
+        let prev_end_time = displayed
+            .iter()
+            .filter_map(|&c| parse_course_datetime(c).map(|(_, end)| end))
+            .filter(|end| *end <= now)
+            .max();
+
+        let progress = if let Some(prev_end) = prev_end_time {
+            let total_free_time = (start - prev_end).num_seconds().max(1) as f64;
+            let elapsed_free_time = (now - prev_end).num_seconds().max(0) as f64;
+            (elapsed_free_time / total_free_time).min(1.0)
+        } else {
+            0.0
+        };
+
+        ClassStatus::Next { course, start, end, progress }
+    }
+
+    pub fn state(&self) -> &'static str {
+        match self {
+            ClassStatus::Ongoing { .. } => "ongoing",
+            ClassStatus::Next { .. } => "next",
+            ClassStatus::None => "none",
+        }
+    }
+
+    /// The course this status is about with its start and end.
+    pub fn course(&self) -> Option<(&'a CourseAction, NaiveDateTime, NaiveDateTime)> {
+        match self {
+            ClassStatus::Ongoing { course, start, end, .. }
+            | ClassStatus::Next { course, start, end, .. } => Some((course, *start, *end)),
+            ClassStatus::None => None,
+        }
+    }
+
+    pub fn progress(&self) -> f64 {
+        match self {
+            ClassStatus::Ongoing { progress, .. } | ClassStatus::Next { progress, .. } => *progress,
+            ClassStatus::None => 0.0,
+        }
+    }
+
+    /// Fills `{placeholder}`s of a status bar template, see `ujep_tui next --help`.
+    pub fn render(&self, template: &str, now: NaiveDateTime) -> String {
+        let Some((course, start, end)) = self.course() else {
+            return String::new();
+        };
+        let until_start = format_short_duration(start - now);
+        let left = format_short_duration(end - now);
+        let when = match self {
+            ClassStatus::Ongoing { .. } => format!("{} left", left),
+            _ => format!("in {}", until_start),
+        };
+
+        let fields = [
+            ("state", self.state().to_string()),
+            ("name", course.name.clone().unwrap_or_default()),
+            ("abbr", course.abbr.clone().unwrap_or_default()),
+            ("dept", course.dept.clone().unwrap_or_default()),
+            ("type", course.class_type.clone().unwrap_or_default()),
+            ("place", course.place.clone().unwrap_or_default()),
+            ("room", course.room.clone().unwrap_or_default()),
+            ("date", course.date.clone().unwrap_or_default()),
+            ("from", course.timeFrom.clone().unwrap_or_default()),
+            ("to", course.timeTo.clone().unwrap_or_default()),
+            ("in", until_start),
+            ("left", left),
+            ("when", when),
+            ("progress", format!("{:.0}", self.progress() * 100.0)),
+        ];
+
+        let mut out = template.to_string();
+        for (key, value) in fields {
+            out = out.replace(&format!("{{{}}}", key), &value);
+        }
+        out
+    }
+
+    pub fn to_json(&self, now: NaiveDateTime) -> serde_json::Value {
+        let Some((course, start, end)) = self.course() else {
+            return json!({ "state": "none" });
+        };
+        json!({
+            "state": self.state(),
+            "course": {
+                "id": course.id,
+                "name": course.name,
+                "dept": course.dept,
+                "abbr": course.abbr,
+                "type": course.class_type,
+                "place": course.place,
+                "room": course.room,
+            },
+            "start": start.format("%Y-%m-%dT%H:%M:%S").to_string(),
+            "end": end.format("%Y-%m-%dT%H:%M:%S").to_string(),
+            "seconds_until_start": (start - now).num_seconds().max(0),
+            "seconds_left": (end - now).num_seconds().max(0),
+            "progress": self.progress(),
+        })
+    }
+}
+
+/// "1h 5m", "23m" or "0m" - short enough for a status bar.
+pub fn format_short_duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes().max(0);
+    if minutes >= 60 {
+        format!("{}h {}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+fn course_json(course: &CourseAction, start: NaiveDateTime, end: NaiveDateTime) -> serde_json::Value {
+    json!({
+        "id": course.id,
+        "name": course.name,
+        "dept": course.dept,
+        "abbr": course.abbr,
+        "type": course.class_type,
+        "place": course.place,
+        "room": course.room,
+        "start": start.format("%Y-%m-%dT%H:%M:%S").to_string(),
+        "end": end.format("%Y-%m-%dT%H:%M:%S").to_string(),
+    })
+}
+
+/// Ongoing and next class together, at minute resolution so that it only
+/// changes when something a subscriber would show changes.
+pub fn snapshot(displayed: &[&CourseAction], now: NaiveDateTime) -> serde_json::Value {
+    let status = ClassStatus::compute(displayed, now);
+    let ongoing = match status {
+        ClassStatus::Ongoing { course, start, end, progress } => {
+            let mut value = course_json(course, start, end);
+            value["minutes_left"] = json!((end - now).num_minutes().max(0));
+            value["progress"] = json!((progress * 100.0).round() as u8);
+            value
+        }
+        _ => serde_json::Value::Null,
+    };
+    let next = displayed
+        .iter()
+        .find_map(|&c| {
+            parse_course_datetime(c)
+                .filter(|(start, _)| *start > now)
+                .map(|(start, end)| (c, start, end))
+        })
+        .map_or(serde_json::Value::Null, |(course, start, end)| {
+            let mut value = course_json(course, start, end);
+            value["minutes_until"] = json!((start - now).num_minutes().max(0));
+            value
+        });
+    let free_time = match status {
+        ClassStatus::Next { progress, .. } => json!((progress * 100.0).round() as u8),
+        _ => serde_json::Value::Null,
+    };
+    json!({
+        "state": status.state(),
+        "text": status.render("{abbr} {when} – {place} {room}", now),
+        "ongoing": ongoing,
+        "next": next,
+        "free_time_percent": free_time,
+    })
+}
@@ -0,0 +1,410 @@
+use std::env;
+use std::sync::OnceLock;
+
+use ratatui::style::{Color, Modifier, Style};
+
+use crate::config::{SlotSpec, ThemeConfig};
+
+/// Every style the screens use, by what it marks rather than by colour.
+#[derive(Clone)]
+pub struct Theme {
+    /// Table headers.
+    pub header: Style,
+    pub selected: Style,
+    /// Highlight of the ongoing and the next class in the table and calendars.
+    pub ongoing: Style,
+    pub next: Style,
+    /// Table rows of overlapping, changed, exam and own event sessions.
+    pub conflict: Style,
+    pub changed: Style,
+    pub exam: Style,
+    pub event: Style,
+    /// Calendar blocks of classes, exams and own events.
+    pub block: Style,
+    pub exam_block: Style,
+    pub event_block: Style,
+    /// Breaks too short to walk to the next class.
+    pub travel: Style,
+    /// Secondary text: hour labels, full exam terms, days outside the month.
+    pub muted: Style,
+    /// Passed exams, added classes.
+    pub good: Style,
+    /// Failed exams, cancelled classes, absences over the limit.
+    pub bad: Style,
+    /// Absences close to the limit, offline mode.
+    pub warning: Style,
+    /// Pending changes, moved classes.
+    pub info: Style,
+    /// Read-only mode, days with exams or own events.
+    pub accent: Style,
+    /// Popups like help and the ignore menu.
+    pub overlay: Style,
+    pub today: Style,
+    /// The current time line in the calendars.
+    pub now: Style,
+    /// Free time everybody shares in the week view.
+    pub free: Style,
+    /// Progress bars, the filled part takes the foreground colour.
+    pub gauge: Style,
+    pub clock: Style,
+    /// Focused and other fields of the login form.
+    pub focus: Style,
+    pub text: Style,
+    /// Month calendar days by contact hours, from light to heavy.
+    pub heat: [Style; 4],
+}
+
+pub const BUILT_IN: [&str; 5] = ["dark", "light", "high-contrast", "colour-blind", "no-color"];
+
+fn fg(color: Color) -> Style {
+    Style::default().fg(color)
+}
+
+fn on(fg: Color, bg: Color) -> Style {
+    Style::default().fg(fg).bg(bg)
+}
+
+fn bold(style: Style) -> Style {
+    style.add_modifier(Modifier::BOLD)
+}
+
+fn plain(modifier: Modifier) -> Style {
+    Style::default().add_modifier(modifier)
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        Theme {
+            header: bold(fg(Color::Yellow)),
+            selected: bold(on(Color::Black, Color::Blue)),
+            ongoing: bold(on(Color::Black, Color::Red)),
+            next: bold(on(Color::Black, Color::Green)),
+            conflict: bold(fg(Color::Red)),
+            changed: bold(fg(Color::Cyan)),
+            exam: bold(fg(Color::Yellow)),
+            event: fg(Color::Magenta),
+            block: on(Color::White, Color::DarkGray),
+            exam_block: bold(on(Color::Black, Color::Yellow)),
+            event_block: on(Color::White, Color::Magenta),
+            travel: bold(fg(Color::Red)),
+            muted: fg(Color::DarkGray),
+            good: fg(Color::Green),
+            bad: bold(fg(Color::Red)),
+            warning: fg(Color::Yellow),
+            info: fg(Color::Cyan),
+            accent: fg(Color::Magenta),
+            overlay: on(Color::White, Color::Black),
+            today: bold(fg(Color::Yellow)),
+            now: bold(fg(Color::Red)),
+            free: on(Color::Black, Color::Green),
+            gauge: fg(Color::Green),
+            clock: bold(fg(Color::Yellow)).add_modifier(Modifier::ITALIC),
+            focus: fg(Color::Yellow),
+            text: fg(Color::White),
+            heat: [
+                on(Color::White, Color::Indexed(22)),
+                on(Color::White, Color::Indexed(64)),
+                on(Color::White, Color::Indexed(136)),
+                on(Color::White, Color::Indexed(124)),
+            ],
+        }
+    }
+
+    /// For dark text on a light background: no white or yellow text.
+    pub fn light() -> Self {
+        Theme {
+            header: bold(fg(Color::Blue)),
+            selected: bold(on(Color::White, Color::Blue)),
+            ongoing: bold(on(Color::White, Color::Red)),
+            next: bold(on(Color::Black, Color::Indexed(114))),
+            conflict: bold(fg(Color::Red)),
+            changed: bold(fg(Color::Indexed(25))),
+            exam: bold(fg(Color::Indexed(130))),
+            event: fg(Color::Magenta),
+            block: on(Color::Black, Color::Indexed(252)),
+            exam_block: bold(on(Color::Black, Color::Indexed(221))),
+            event_block: on(Color::Black, Color::Indexed(219)),
+            travel: bold(fg(Color::Red)),
+            muted: fg(Color::Indexed(244)),
+            good: fg(Color::Indexed(28)),
+            bad: bold(fg(Color::Red)),
+            warning: fg(Color::Indexed(130)),
+            info: fg(Color::Indexed(25)),
+            accent: fg(Color::Magenta),
+            overlay: on(Color::Black, Color::White),
+            today: bold(fg(Color::Blue)),
+            now: bold(fg(Color::Red)),
+            free: on(Color::Black, Color::Indexed(157)),
+            gauge: fg(Color::Indexed(28)),
+            clock: bold(fg(Color::Blue)).add_modifier(Modifier::ITALIC),
+            focus: bold(fg(Color::Blue)),
+            text: fg(Color::Reset),
+            heat: [
+                on(Color::Black, Color::Indexed(194)),
+                on(Color::Black, Color::Indexed(150)),
+                on(Color::Black, Color::Indexed(222)),
+                on(Color::Black, Color::Indexed(210)),
+            ],
+        }
+    }
+
+    /// Bright colours only, and highlights that do not rely on colour alone.
+    pub fn high_contrast() -> Self {
+        let underlined = |style: Style| style.add_modifier(Modifier::UNDERLINED);
+        Theme {
+            header: underlined(bold(fg(Color::White))),
+            selected: bold(on(Color::Black, Color::White)),
+            ongoing: bold(on(Color::Black, Color::LightRed)),
+            next: bold(on(Color::Black, Color::LightGreen)),
+            conflict: underlined(bold(fg(Color::LightRed))),
+            changed: bold(fg(Color::LightCyan)),
+            exam: bold(fg(Color::LightYellow)),
+            event: bold(fg(Color::LightMagenta)),
+            block: bold(on(Color::Black, Color::Gray)),
+            exam_block: bold(on(Color::Black, Color::LightYellow)),
+            event_block: bold(on(Color::Black, Color::LightMagenta)),
+            travel: underlined(bold(fg(Color::LightRed))),
+            muted: fg(Color::Gray),
+            good: bold(fg(Color::LightGreen)),
+            bad: underlined(bold(fg(Color::LightRed))),
+            warning: bold(fg(Color::LightYellow)),
+            info: bold(fg(Color::LightCyan)),
+            accent: bold(fg(Color::LightMagenta)),
+            overlay: on(Color::White, Color::Black),
+            today: underlined(bold(fg(Color::LightYellow))),
+            now: bold(fg(Color::LightRed)),
+            free: bold(on(Color::Black, Color::LightGreen)),
+            gauge: fg(Color::White),
+            clock: bold(fg(Color::White)),
+            focus: bold(on(Color::Black, Color::LightYellow)),
+            text: fg(Color::White),
+            heat: [
+                on(Color::White, Color::Indexed(236)),
+                on(Color::White, Color::Indexed(240)),
+                on(Color::Black, Color::Indexed(248)),
+                bold(on(Color::Black, Color::White)),
+            ],
+        }
+    }
+
+    /// Blue and orange instead of green and red, a viridis scale for the month.
+    pub fn colour_blind() -> Self {
+        let (blue, orange, yellow, purple) =
+            (Color::Indexed(39), Color::Indexed(208), Color::Indexed(220), Color::Indexed(175));
+        Theme {
+            header: bold(fg(yellow)),
+            selected: bold(on(Color::Black, Color::White)),
+            ongoing: bold(on(Color::Black, orange)),
+            next: bold(on(Color::Black, blue)),
+            conflict: bold(fg(orange)).add_modifier(Modifier::UNDERLINED),
+            changed: bold(fg(blue)),
+            exam: bold(fg(yellow)),
+            event: fg(purple),
+            block: on(Color::White, Color::DarkGray),
+            exam_block: bold(on(Color::Black, yellow)),
+            event_block: on(Color::Black, purple),
+            travel: bold(fg(orange)),
+            muted: fg(Color::DarkGray),
+            good: fg(blue),
+            bad: bold(fg(orange)),
+            warning: fg(yellow),
+            info: fg(blue),
+            accent: fg(purple),
+            overlay: on(Color::White, Color::Black),
+            today: bold(fg(yellow)),
+            now: bold(fg(orange)),
+            free: on(Color::Black, blue),
+            gauge: fg(blue),
+            clock: bold(fg(yellow)).add_modifier(Modifier::ITALIC),
+            focus: fg(yellow),
+            text: fg(Color::White),
+            heat: [
+                on(Color::White, Color::Indexed(54)),
+                on(Color::White, Color::Indexed(24)),
+                on(Color::Black, Color::Indexed(36)),
+                on(Color::Black, Color::Indexed(178)),
+            ],
+        }
+    }
+
+    /// No colours at all, see https://no-color.org. Highlights use reverse video.
+    pub fn no_color() -> Self {
+        Theme {
+            header: plain(Modifier::BOLD),
+            selected: plain(Modifier::REVERSED | Modifier::BOLD),
+            ongoing: plain(Modifier::REVERSED),
+            next: plain(Modifier::BOLD | Modifier::UNDERLINED),
+            conflict: plain(Modifier::BOLD),
+            changed: plain(Modifier::BOLD),
+            exam: plain(Modifier::BOLD),
+            event: plain(Modifier::ITALIC),
+            block: plain(Modifier::REVERSED),
+            exam_block: plain(Modifier::REVERSED | Modifier::BOLD),
+            event_block: plain(Modifier::REVERSED | Modifier::ITALIC),
+            travel: plain(Modifier::BOLD),
+            muted: plain(Modifier::DIM),
+            good: Style::default(),
+            bad: plain(Modifier::BOLD),
+            warning: plain(Modifier::BOLD),
+            info: Style::default(),
+            accent: plain(Modifier::BOLD),
+            overlay: Style::default(),
+            today: plain(Modifier::BOLD | Modifier::UNDERLINED),
+            now: plain(Modifier::BOLD),
+            free: plain(Modifier::BOLD | Modifier::UNDERLINED),
+            gauge: Style::default(),
+            clock: plain(Modifier::BOLD),
+            focus: plain(Modifier::REVERSED),
+            text: Style::default(),
+            heat: [
+                plain(Modifier::DIM),
+                Style::default(),
+                plain(Modifier::BOLD),
+                plain(Modifier::REVERSED),
+            ],
+        }
+    }
+
+    pub fn built_in(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Theme::dark()),
+            "light" => Some(Theme::light()),
+            "high-contrast" => Some(Theme::high_contrast()),
+            "colour-blind" | "color-blind" => Some(Theme::colour_blind()),
+            "no-color" | "no-colour" => Some(Theme::no_color()),
+            _ => None,
+        }
+    }
+
+    fn slot_mut(&mut self, name: &str) -> Option<&mut Style> {
+        Some(match name {
+            "header" => &mut self.header,
+            "selected" => &mut self.selected,
+            "ongoing" => &mut self.ongoing,
+            "next" => &mut self.next,
+            "conflict" => &mut self.conflict,
+            "changed" => &mut self.changed,
+            "exam" => &mut self.exam,
+            "event" => &mut self.event,
+            "block" => &mut self.block,
+            "exam_block" => &mut self.exam_block,
+            "event_block" => &mut self.event_block,
+            "travel" => &mut self.travel,
+            "muted" => &mut self.muted,
+            "good" => &mut self.good,
+            "bad" => &mut self.bad,
+            "warning" => &mut self.warning,
+            "info" => &mut self.info,
+            "accent" => &mut self.accent,
+            "overlay" => &mut self.overlay,
+            "today" => &mut self.today,
+            "now" => &mut self.now,
+            "free" => &mut self.free,
+            "gauge" => &mut self.gauge,
+            "clock" => &mut self.clock,
+            "focus" => &mut self.focus,
+            "text" => &mut self.text,
+            "heat1" => &mut self.heat[0],
+            "heat2" => &mut self.heat[1],
+            "heat3" => &mut self.heat[2],
+            "heat4" => &mut self.heat[3],
+            _ => return None,
+        })
+    }
+
+    /// The theme to use: `name` if given, else the configured one. Without either
+    /// a set `NO_COLOR` turns colours off.
+    pub fn from_config(config: &ThemeConfig, name: Option<&str>) -> Result<Self, Box<dyn std::error::Error>> {
+        let name = match name.or(config.name.as_deref()) {
+            Some(name) => name,
+            None if env::var("NO_COLOR").is_ok_and(|value| !value.is_empty()) => "no-color",
+            None => "dark",
+        };
+        if let Some(theme) = Theme::built_in(name) {
+            return Ok(theme);
+        }
+        let own = config.themes.get(name).ok_or_else(|| {
+            format!("Unknown theme '{}', built in are {}", name, BUILT_IN.join(", "))
+        })?;
+        let mut theme = Theme::built_in(&own.base)
+            .ok_or_else(|| format!("Theme '{}' is based on '{}', which is not built in", name, own.base))?;
+        for (slot, spec) in &own.slots {
+            let style = theme
+                .slot_mut(slot)
+                .ok_or_else(|| format!("Theme '{}' has an unknown style slot '{}'", name, slot))?;
+            *style = spec.style().map_err(|e| format!("Theme '{}', slot '{}': {}", name, slot, e))?;
+        }
+        Ok(theme)
+    }
+}
+
+/// `red`, `light-blue`, `#ff8800` or a 256-colour index like `208`.
+pub fn parse_color(value: &str) -> Result<Color, String> {
+    let name = value.to_lowercase().replace(['-', '_', ' '], "");
+    Ok(match name.as_str() {
+        "reset" | "default" => Color::Reset,
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "gray" | "grey" => Color::Gray,
+        "darkgray" | "darkgrey" => Color::DarkGray,
+        "lightred" => Color::LightRed,
+        "lightgreen" => Color::LightGreen,
+        "lightyellow" => Color::LightYellow,
+        "lightblue" => Color::LightBlue,
+        "lightmagenta" => Color::LightMagenta,
+        "lightcyan" => Color::LightCyan,
+        "white" => Color::White,
+        _ => {
+            if let Some(hex) = name.strip_prefix('#').filter(|hex| hex.len() == 6) {
+                let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16);
+                match (channel(0), channel(2), channel(4)) {
+                    (Ok(r), Ok(g), Ok(b)) => Color::Rgb(r, g, b),
+                    _ => return Err(format!("invalid colour '{}'", value)),
+                }
+            } else {
+                Color::Indexed(name.parse().map_err(|_| format!("invalid colour '{}'", value))?)
+            }
+        }
+    })
+}
+
+impl SlotSpec {
+    pub fn style(&self) -> Result<Style, String> {
+        let mut style = Style::default();
+        if let Some(color) = &self.fg {
+            style = style.fg(parse_color(color)?);
+        }
+        if let Some(color) = &self.bg {
+            style = style.bg(parse_color(color)?);
+        }
+        for (on, modifier) in [
+            (self.bold, Modifier::BOLD),
+            (self.italic, Modifier::ITALIC),
+            (self.underlined, Modifier::UNDERLINED),
+            (self.reversed, Modifier::REVERSED),
+            (self.dim, Modifier::DIM),
+        ] {
+            if on {
+                style = style.add_modifier(modifier);
+            }
+        }
+        Ok(style)
+    }
+}
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Picks the theme for the rest of the run, only the first call counts.
+pub fn set_theme(theme: Theme) {
+    let _ = THEME.set(theme);
+}
+
+/// The theme in use, dark until one is set.
+pub fn theme() -> &'static Theme {
+    THEME.get_or_init(Theme::dark)
+}
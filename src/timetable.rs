@@ -1,21 +1,40 @@
-use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
 use serde::Deserialize;
 
+#[allow(non_snake_case, dead_code)]
 #[derive(Debug, Deserialize)]
 pub struct Timetable {
+    #[serde(default)]
     pub code: u32,
+    #[serde(default)]
     pub message: String,
+    #[serde(default)]
     pub statusCode: u32,
     pub data: Data,
+    #[serde(default)]
     pub retrieved_at: String,
 }
 
+#[allow(non_snake_case)]
 #[derive(Debug, Deserialize)]
 pub struct Data {
     pub courseActions: Vec<CourseAction>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+impl Timetable {
+    pub fn from_course_actions(course_actions: Vec<CourseAction>, retrieved_at: String) -> Self {
+        Timetable {
+            code: 0,
+            message: String::new(),
+            statusCode: 0,
+            data: Data { courseActions: course_actions },
+            retrieved_at,
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Deserialize, Clone, Default)]
 pub struct CourseAction {
     pub id: Option<u32>,
     pub name: Option<String>,
@@ -50,6 +69,24 @@ pub fn parse_course_datetime(course: &CourseAction) -> Option<(NaiveDateTime, Na
     Some((date.and_time(start_time), date.and_time(end_time)))
 }
 
+/// Action ids repeat every week, so a session is its id plus its date.
+pub fn session_key(course: &CourseAction) -> Option<String> {
+    let (start, _) = parse_course_datetime(course)?;
+    Some(format!("{}-{}", course.id?, start.format("%Y%m%d")))
+}
+
+/// A timed action for tests, e.g. `test_course(1, "21.10.2026", "13:00", "13:50")`.
+#[cfg(test)]
+pub fn test_course(id: u32, date: &str, from: &str, to: &str) -> CourseAction {
+    CourseAction {
+        id: Some(id),
+        abbr: Some(format!("C{}", id)),
+        date: Some(date.to_string()),
+        timeFrom: Some(from.to_string()),
+        timeTo: Some(to.to_string()),
+        ..Default::default()
+    }
+}
 
 pub fn is_course_ongoing(course: &CourseAction, now: NaiveDateTime) -> bool {
     if let Some((start_dt, end_dt)) = parse_course_datetime(course) {
@@ -58,3 +95,17 @@ pub fn is_course_ongoing(course: &CourseAction, now: NaiveDateTime) -> bool {
         false
     }
 }
+
+
+/// Czech weekday abbreviation as the API spells it once diacritics are stripped.
+pub fn weekday_abbr(date: NaiveDate) -> &'static str {
+    match date.weekday() {
+        Weekday::Mon => "Po",
+        Weekday::Tue => "Ut",
+        Weekday::Wed => "St",
+        Weekday::Thu => "Ct",
+        Weekday::Fri => "Pa",
+        Weekday::Sat => "So",
+        Weekday::Sun => "Ne",
+    }
+}
@@ -0,0 +1,149 @@
+use chrono::{Duration, NaiveDateTime};
+
+use crate::config::TravelConfig;
+use crate::timetable::{parse_course_datetime, session_key, CourseAction};
+
+/// A class that starts sooner after the previous one than it takes to walk there.
+pub struct TravelWarning<'c> {
+    pub from: &'c CourseAction,
+    pub to: &'c CourseAction,
+    pub break_minutes: i64,
+    pub travel_minutes: i64,
+    /// Latest time to set off from the previous class.
+    pub leave_at: NaiveDateTime,
+}
+
+impl TravelWarning<'_> {
+    pub fn destination(&self) -> String {
+        format!("{} {}", self.to.place.as_deref().unwrap_or(""), self.to.room.as_deref().unwrap_or(""))
+    }
+
+    /// E.g. "10m break, 15m walk CP -> MF".
+    pub fn text(&self) -> String {
+        format!(
+            "{}m break, {}m walk {} -> {}",
+            self.break_minutes,
+            self.travel_minutes,
+            self.from.place.as_deref().unwrap_or(""),
+            self.to.place.as_deref().unwrap_or("")
+        )
+    }
+}
+
+/// Back-to-back classes in different places with too short a break between
+/// them. Overlapping classes are left to the conflict check.
+pub fn travel_warnings<'c>(courses: &[&'c CourseAction], config: &TravelConfig) -> Vec<TravelWarning<'c>> {
+    let mut timed: Vec<(NaiveDateTime, NaiveDateTime, &CourseAction)> = courses
+        .iter()
+        .filter_map(|&course| parse_course_datetime(course).map(|(start, end)| (start, end, course)))
+        .collect();
+    timed.sort_by_key(|&(start, end, _)| (start, end));
+
+    let mut warnings = Vec::new();
+    // The class of the day that ends last so far, where the way starts.
+    let mut previous: Option<(NaiveDateTime, &CourseAction)> = None;
+    for (start, end, course) in timed {
+        if let Some((previous_end, from)) = previous.filter(|(previous_end, _)| previous_end.date() == start.date()) {
+            let travel = from.place.as_deref().zip(course.place.as_deref()).and_then(|(a, b)| config.minutes(a, b));
+            if let Some(travel_minutes) = travel.filter(|_| start >= previous_end) {
+                let break_minutes = (start - previous_end).num_minutes();
+                if break_minutes < travel_minutes {
+                    warnings.push(TravelWarning {
+                        from,
+                        to: course,
+                        break_minutes,
+                        travel_minutes,
+                        leave_at: start - Duration::minutes(travel_minutes),
+                    });
+                }
+            }
+        }
+        if previous.is_none_or(|(previous_end, _)| end > previous_end || previous_end.date() != start.date()) {
+            previous = Some((end, course));
+        }
+    }
+    warnings
+}
+
+/// The warning about getting to `course`, if there is one.
+pub fn warning_for<'w, 'c>(warnings: &'w [TravelWarning<'c>], course: &CourseAction) -> Option<&'w TravelWarning<'c>> {
+    let key = session_key(course)?;
+    warnings.iter().find(|warning| session_key(warning.to).as_ref() == Some(&key))
+}
+
+/// The warning to show while it is time to set off, until the class starts.
+pub fn leave_now<'w, 'c>(warnings: &'w [TravelWarning<'c>], now: NaiveDateTime) -> Option<&'w TravelWarning<'c>> {
+    warnings.iter().find(|warning| {
+        now >= warning.leave_at && parse_course_datetime(warning.to).is_some_and(|(start, _)| now < start)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::timetable::test_course;
+
+    fn at(place: &str, id: u32, date: &str, from: &str, to: &str) -> CourseAction {
+        let mut course = test_course(id, date, from, to);
+        course.place = Some(place.to_string());
+        course
+    }
+
+    fn config() -> TravelConfig {
+        TravelConfig { minutes: HashMap::from([("CP".to_string(), HashMap::from([("MF".to_string(), 15)]))]) }
+    }
+
+    #[test]
+    fn short_break_between_places_warns() {
+        let (a, b) = (at("CP", 1, "21.10.2026", "11:00", "12:50"), at("MF", 2, "21.10.2026", "13:00", "13:50"));
+        let warnings = travel_warnings(&[&b, &a], &config());
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].to.id, Some(2));
+        assert_eq!(warnings[0].text(), "10m break, 15m walk CP -> MF");
+        assert_eq!(warnings[0].leave_at.format("%H:%M").to_string(), "12:45");
+        assert!(warning_for(&warnings, &b).is_some() && warning_for(&warnings, &a).is_none());
+    }
+
+    #[test]
+    fn long_enough_break_same_place_and_unknown_pairs_do_not_warn() {
+        let config = config();
+        let (a, b) = (at("MF", 1, "21.10.2026", "11:00", "12:45"), at("CP", 2, "21.10.2026", "13:00", "13:50"));
+        assert!(travel_warnings(&[&a, &b], &config).is_empty());
+        let (a, b) = (at("CP", 1, "21.10.2026", "11:00", "12:50"), at("CP", 2, "21.10.2026", "13:00", "13:50"));
+        assert!(travel_warnings(&[&a, &b], &config).is_empty());
+        let (a, b) = (at("CP", 1, "21.10.2026", "11:00", "12:50"), at("SOC", 2, "21.10.2026", "13:00", "13:50"));
+        assert!(travel_warnings(&[&a, &b], &config).is_empty());
+    }
+
+    #[test]
+    fn overlaps_and_other_days_do_not_warn() {
+        let config = config();
+        let (a, b) = (at("CP", 1, "21.10.2026", "11:00", "12:50"), at("MF", 2, "21.10.2026", "12:00", "13:50"));
+        assert!(travel_warnings(&[&a, &b], &config).is_empty());
+        let (a, b) = (at("CP", 1, "21.10.2026", "17:00", "18:50"), at("MF", 2, "22.10.2026", "07:00", "08:50"));
+        assert!(travel_warnings(&[&a, &b], &config).is_empty());
+    }
+
+    #[test]
+    fn way_starts_at_the_class_that_ends_last() {
+        // A short class inside a long one, the walk starts when the long one ends.
+        let long = at("MF", 1, "21.10.2026", "09:00", "12:50");
+        let short = at("CP", 2, "21.10.2026", "10:00", "10:50");
+        let next = at("CP", 3, "21.10.2026", "13:00", "13:50");
+        let warnings = travel_warnings(&[&long, &short, &next], &config());
+        assert_eq!(warnings.len(), 1);
+        assert_eq!((warnings[0].from.id, warnings[0].to.id), (Some(1), Some(3)));
+    }
+
+    #[test]
+    fn leave_now_between_leave_time_and_start() {
+        let (a, b) = (at("CP", 1, "21.10.2026", "11:00", "12:50"), at("MF", 2, "21.10.2026", "13:00", "13:50"));
+        let warnings = travel_warnings(&[&a, &b], &config());
+        let time = |hm: &str| NaiveDateTime::parse_from_str(&format!("21.10.2026 {}", hm), "%d.%m.%Y %H:%M").unwrap();
+        assert!(leave_now(&warnings, time("12:40")).is_none());
+        assert!(leave_now(&warnings, time("12:45")).is_some());
+        assert!(leave_now(&warnings, time("13:00")).is_none());
+    }
+}
@@ -1,145 +1,64 @@
 use std::{cmp, io};
 use chrono::{Local, NaiveDateTime};
-use crossterm::event::{self, Event, KeyCode};
+use crossterm::event::{self, Event, KeyCode, KeyModifiers};
 use ratatui::{
     backend::Backend,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Span, Spans, Text},
     widgets::{Block, Borders, Clear, Gauge, Paragraph, Row, Table, Wrap},
     Terminal,
 };
 
-use crate::app::App;
-use crate::timetable::{is_course_ongoing, parse_course_datetime, CourseAction};
+use crate::app::{App, View};
+use crate::changes::{save_changes, ChangeKind};
+use crate::conflicts::{conflicting_sessions, find_conflicts};
+use crate::conflicts_view::{draw_conflicts_view, handle_conflicts_key, upcoming_conflicts};
+use crate::courses_view::{draw_courses_view, handle_courses_key};
+use crate::day_view::{draw_day_view, handle_day_key};
+use crate::events::is_user_event;
+use crate::exams::is_exam;
+use crate::exams_view::{draw_exams_view, handle_exams_key};
+use crate::free_view::{draw_free_view, handle_free_key};
+use crate::export::{export_path, to_csv, to_ics, to_json};
+use crate::month_view::{draw_month_view, handle_month_key};
+use crate::notes::{course_key, NoteEditor, NoteTarget};
+use crate::places_view::{draw_places_view, handle_places_key};
+use crate::results_view::{draw_results_view, handle_results_key};
+use crate::search::matches_query;
+use crate::status::ClassStatus;
+use crate::theme::theme;
+use crate::timetable::{is_course_ongoing, parse_course_datetime, session_key, CourseAction};
+use crate::travel::{leave_now, travel_warnings, warning_for, TravelWarning};
+use crate::week_view::{draw_week_view, handle_week_key};
 use std::fs;
 use std::path::PathBuf;
-use dirs;
-
-
-struct LoweredFields {
-    class_type: String,
-    name: String,
-    dept: String,
-    abbr: String,
-    year: String,
-    semester: String,
-    date: String,
-    time_from: String,
-    time_to: String,
-    place: String,
-    room: String,
-    day: String,
-    week_type: String,
-    week_from: String,
-    week_to: String,
-    note: String,
-    contact: String,
-    statut: String,
-    teacher_id: String,
-}
-
-impl LoweredFields {
-    fn new(course: &CourseAction) -> Self {
-        Self {
-            class_type: course.class_type.as_deref().unwrap_or("").to_lowercase(),
-            name: course.name.as_deref().unwrap_or("").to_lowercase(),
-            dept: course.dept.as_deref().unwrap_or("").to_lowercase(),
-            abbr: course.abbr.as_deref().unwrap_or("").to_lowercase(),
-            year: course.year.as_deref().unwrap_or("").to_lowercase(),
-            semester: course.semester.as_deref().unwrap_or("").to_lowercase(),
-            date: course.date.as_deref().unwrap_or("").to_lowercase(),
-            time_from: course.timeFrom.as_deref().unwrap_or("").to_lowercase(),
-            time_to: course.timeTo.as_deref().unwrap_or("").to_lowercase(),
-            place: course.place.as_deref().unwrap_or("").to_lowercase(),
-            room: course.room.as_deref().unwrap_or("").to_lowercase(),
-            day: course.day.as_deref().unwrap_or("").to_lowercase(),
-            week_type: course.weekType.as_deref().unwrap_or("").to_lowercase(),
-            week_from: course.weekFrom.map_or(String::new(), |v| v.to_string()),
-            week_to: course.weekTo.map_or(String::new(), |v| v.to_string()),
-            note: course.note.as_deref().unwrap_or("").to_lowercase(),
-            contact: course.contact.as_deref().unwrap_or("").to_lowercase(),
-            statut: course.statut.as_deref().unwrap_or("").to_lowercase(),
-            teacher_id: course.teachingTeacherStagId.map_or(String::new(), |v| v.to_string()),
-        }
-    }
 
-    
-    fn contains_any(&self, q: &str) -> bool {
-        self.name.contains(q)
-            || self.dept.contains(q)
-            || self.abbr.contains(q)
-            || self.year.contains(q)
-            || self.semester.contains(q)
-            || self.date.contains(q)
-            || self.time_from.contains(q)
-            || self.time_to.contains(q)
-            || self.place.contains(q)
-            || self.room.contains(q)
-            || self.class_type.contains(q)
-            || self.day.contains(q)
-            || self.week_type.contains(q)
-            || self.week_from.contains(q)
-            || self.week_to.contains(q)
-            || self.note.contains(q)
-            || self.contact.contains(q)
-            || self.statut.contains(q)
-            || self.teacher_id.contains(q)
-    }
-}
 
 pub fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<()> {
     loop {
         let now = Local::now().naive_local();
 
-        
-        let mut displayed: Vec<&CourseAction> = app
+        let active: Vec<&CourseAction> = app
             .courses
             .iter()
-            .filter(|&course| course.id.map_or(true, |id| !app.ignored_ids.contains(&id)))
+            .filter(|&course| course.id.is_none_or(|id| !app.ignored_ids.contains(&id)))
             .copied()
             .collect();
+        if let Some(reminders) = app.reminders.as_mut() {
+            reminders.tick(&active, now);
+        }
+        // Ignoring a class can resolve a conflict or free time, unlike searching.
+        app.conflicts = find_conflicts(&active);
+        app.conflicting_sessions = conflicting_sessions(&app.conflicts);
+        app.travel_warnings = travel_warnings(&active, &app.travel);
 
         
-        if let Some(ref query) = app.search_query {
-            if !query.is_empty() {
-                let q_lower = query.to_lowercase();
-                let mut parts = q_lower.splitn(2, ':');
-                let (field, q_part) = if let (Some(field), Some(q_part)) = (parts.next(), parts.next()) {
-                    (field, q_part)
-                } else {
-                    ("", q_lower.as_str())
-                };
+        let mut displayed = active.clone();
 
-                displayed.retain(|course| {
-                    
-                    let lf = LoweredFields::new(course);
-
-                    match field {
-                        "type" => lf.class_type.contains(q_part),
-                        "name" => lf.name.contains(q_part),
-                        "dept" => lf.dept.contains(q_part),
-                        "abbr" => lf.abbr.contains(q_part),
-                        "year" => lf.year.contains(q_part),
-                        "semester" => lf.semester.contains(q_part),
-                        "date" => lf.date.contains(q_part),
-                        "timefrom" => lf.time_from.contains(q_part),
-                        "timeto" => lf.time_to.contains(q_part),
-                        "place" => lf.place.contains(q_part),
-                        "room" => lf.room.contains(q_part),
-                        "day" => lf.day.contains(q_part),
-                        "weektype" => lf.week_type.contains(q_part),
-                        "weekfrom" => lf.week_from.contains(q_part),
-                        "weekto" => lf.week_to.contains(q_part),
-                        "note" => lf.note.contains(q_part),
-                        "contact" => lf.contact.contains(q_part),
-                        "statut" => lf.statut.contains(q_part),
-                        "teacherid" => lf.teacher_id.contains(q_part),
-                        
-                        _ => lf.contains_any(q_part),
-                    }
-                });
-            }
+        
+        if let Some(ref query) = app.search_query {
+            displayed.retain(|course| matches_query(course, app.notes.text(course).as_deref(), query));
         }
 
         
@@ -226,7 +145,7 @@ pub fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Res
             };
 
             
-            let (status_text, gauge_data) = build_status_msg(&displayed, now);
+            let (status_text, gauge_data) = build_status_msg(&displayed, &app.travel_warnings, now);
             let status_paragraph = Paragraph::new(status_text)
                 .block(Block::default().borders(Borders::ALL).title("Status"))
                 .alignment(Alignment::Center)
@@ -237,7 +156,7 @@ pub fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Res
                 if progress > 0.0 {
                     let gauge = Gauge::default()
                         .block(Block::default())
-                        .gauge_style(Style::default().fg(Color::Green))
+                        .gauge_style(theme().gauge)
                         .ratio(progress)
                         .label(label);
                     let gauge_area = Rect {
@@ -275,6 +194,16 @@ pub fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Res
                 &[]
             };
 
+            // Combined labels like "NEXT CONFLICT" widen the column when they show up.
+            let indicator_width = visible_slice
+                .iter()
+                .enumerate()
+                .map(|(i, course)| row_indicator(course, app.scroll_offset + i, next_index, app, now).chars().count())
+                .max()
+                .unwrap_or(0)
+                .max(9) as u16
+                + 1;
+
             let rows: Vec<Row> = visible_slice
                 .iter()
                 .enumerate()
@@ -292,6 +221,16 @@ pub fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Res
                 })
                 .collect();
 
+            let widths = [
+                Constraint::Length(indicator_width),
+                Constraint::Length(5),
+                Constraint::Length(12),
+                Constraint::Length(16),
+                Constraint::Length(6),
+                Constraint::Percentage(25),
+                Constraint::Length(6),
+                Constraint::Length(8),
+            ];
             let table = Table::new(rows)
                 .header(
                     Row::new(vec![
@@ -304,20 +243,22 @@ pub fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Res
                         "Place",
                         "Room",
                     ])
-                    .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                    .style(theme().header),
                 )
                 .block(Block::default().borders(Borders::ALL).title("Upcoming Classes"))
-                .widths(&[
-                    Constraint::Length(10),
-                    Constraint::Length(5),
-                    Constraint::Length(12),
-                    Constraint::Length(16),
-                    Constraint::Length(6),
-                    Constraint::Percentage(25),
-                    Constraint::Length(6),
-                    Constraint::Length(8),
-                ]);
-            f.render_widget(table, table_area);
+                .widths(&widths);
+            match app.view {
+                View::Table => f.render_widget(table, table_area),
+                View::Week => draw_week_view(f, table_area, app, &displayed, now),
+                View::Day => draw_day_view(f, table_area, app, &displayed, now),
+                View::Month => draw_month_view(f, table_area, app, &displayed, now),
+                View::Courses => draw_courses_view(f, table_area, app, &displayed, now),
+                View::Exams => draw_exams_view(f, table_area, app, now),
+                View::Results => draw_results_view(f, table_area, app),
+                View::Conflicts => draw_conflicts_view(f, table_area, app, now),
+                View::Free => draw_free_view(f, table_area, app, &active, now),
+                View::Places => draw_places_view(f, table_area, app),
+            }
 
             
             let last_update_text = if let Some(last_update) = app.last_update {
@@ -325,7 +266,8 @@ pub fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Res
             } else {
                 "N/A".to_string()
             };
-            let last_update_block = Block::default().borders(Borders::ALL).title("Last [s]ync");
+            let last_update_title = if app.read_only { "Retrieved" } else { "Last [s]ync" };
+            let last_update_block = Block::default().borders(Borders::ALL).title(last_update_title);
             let last_update_area = Rect {
                 x: size.width.saturating_sub(23),
                 y: 0,
@@ -372,7 +314,54 @@ pub fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Res
             f.render_widget(Clear, ignored_count_area);
             f.render_widget(ignored_count_paragraph, ignored_count_area);
 
-            if app.offline_mode {
+            let mut left_box_x = ignored_count_area.x;
+            if !app.changes.is_empty() {
+                let changes_label = format!("[u]pdates: {}", app.changes.len());
+                let changes_area = Rect {
+                    x: left_box_x.saturating_sub(changes_label.len() as u16 + 2),
+                    width: changes_label.len() as u16 + 2,
+                    ..ignored_count_area
+                };
+                let changes_paragraph = Paragraph::new(changes_label)
+                    .block(Block::default().borders(Borders::ALL))
+                    .alignment(Alignment::Center)
+                    .style(theme().info);
+                f.render_widget(Clear, changes_area);
+                f.render_widget(changes_paragraph, changes_area);
+                left_box_x = changes_area.x;
+            }
+
+            let upcoming_conflicts = upcoming_conflicts(&app.conflicts, now).len();
+            if upcoming_conflicts > 0 {
+                let conflicts_label = format!("[o]verlaps: {}", upcoming_conflicts);
+                let conflicts_area = Rect {
+                    x: left_box_x.saturating_sub(conflicts_label.len() as u16 + 2),
+                    width: conflicts_label.len() as u16 + 2,
+                    ..ignored_count_area
+                };
+                let conflicts_paragraph = Paragraph::new(conflicts_label)
+                    .block(Block::default().borders(Borders::ALL))
+                    .alignment(Alignment::Center)
+                    .style(theme().conflict);
+                f.render_widget(Clear, conflicts_area);
+                f.render_widget(conflicts_paragraph, conflicts_area);
+            }
+
+            if app.read_only {
+                let read_only_label = "Read-only";
+                let read_only_area = Rect {
+                    x: 0,
+                    y: size.height.saturating_sub(3),
+                    width: read_only_label.len() as u16 + 2,
+                    height: 3,
+                };
+                let read_only_paragraph = Paragraph::new(read_only_label)
+                    .block(Block::default().borders(Borders::ALL))
+                    .alignment(Alignment::Center)
+                    .style(theme().accent);
+                f.render_widget(Clear, read_only_area);
+                f.render_widget(read_only_paragraph, read_only_area);
+            } else if app.offline_mode {
                 let offline_label = "Offline";
                 let offline_area = Rect {
                     x: 0,
@@ -383,7 +372,7 @@ pub fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Res
                 let offline_paragraph = Paragraph::new(offline_label)
                     .block(Block::default().borders(Borders::ALL))
                     .alignment(Alignment::Center)
-                    .style(Style::default().fg(Color::Yellow));
+                    .style(theme().warning);
                 f.render_widget(Clear, offline_area);
                 f.render_widget(offline_paragraph, offline_area);
             }
@@ -469,17 +458,17 @@ pub fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Res
                 for line in 0..7 {
                     for ch in time_str.chars() {
                         let digit = match ch {
-                            '0' => &ascii_digits[0],
-                            '1' => &ascii_digits[1],
-                            '2' => &ascii_digits[2],
-                            '3' => &ascii_digits[3],
-                            '4' => &ascii_digits[4],
-                            '5' => &ascii_digits[5],
-                            '6' => &ascii_digits[6],
-                            '7' => &ascii_digits[7],
-                            '8' => &ascii_digits[8],
-                            '9' => &ascii_digits[9],
-                            ':' => &ascii_digits[10],
+                            '0' => ascii_digits[0],
+                            '1' => ascii_digits[1],
+                            '2' => ascii_digits[2],
+                            '3' => ascii_digits[3],
+                            '4' => ascii_digits[4],
+                            '5' => ascii_digits[5],
+                            '6' => ascii_digits[6],
+                            '7' => ascii_digits[7],
+                            '8' => ascii_digits[8],
+                            '9' => ascii_digits[9],
+                            ':' => ascii_digits[10],
                             _ => "",
                         };
                         let digit_lines: Vec<&str> = digit.split('\n').collect();
@@ -493,12 +482,7 @@ pub fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Res
                 let time_paragraph = Paragraph::new(ascii_time)
                     .block(time_block)
                     .alignment(Alignment::Center)
-                    .style(
-                        Style::default()
-                            .add_modifier(Modifier::BOLD)
-                            .fg(Color::Yellow)
-                            .add_modifier(Modifier::ITALIC),
-                    );
+                    .style(theme().clock);
                 let centered_area = Rect {
                     x: 0,
                     y: size.height / 2 - 4,
@@ -514,37 +498,66 @@ pub fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Res
             }
 
             
+            if app.export_overlay_active {
+                draw_export_overlay(f, size, app, final_displayed.len());
+            }
+
+            if app.show_changes {
+                draw_changes_overlay(f, size, app);
+            }
+
+            
             if app.show_details {
-                if let Some(selected) = app.selected {
+                if let Some(course) = app.details_course {
+                    let is_next = next_course.is_some_and(|next| std::ptr::eq(*next, course));
+                    draw_course_details(f, size, course, is_next, now, app);
+                } else if let Some(selected) = app.selected {
                     if let Some(course) = final_displayed.get(selected) {
-                        draw_course_details(f, size, course, selected, next_index, now, app);
+                        draw_course_details(f, size, course, Some(selected) == next_index, now, app);
                     }
                 }
             }
 
+            if let Some(editor) = &app.note_editor {
+                draw_note_editor(f, size, editor);
+            }
+
             
             if app.show_help {
                 let help_text = r#"[Home/End]: Jump to first/last item
 [Up/Down][j/k]: Move selection
 [Enter][l]: Show details
+[n/N] in details: Own note for the session/course
 [s]: Sync the timetable
 [i]: Toggle ignore menu
+[e]: Export shown classes
+[w]: Week view
+[d]: Day view
+[m]: Month calendar
+[c]: Courses overview
+[x]: Exam terms
+[r]: Study results and credits
+[u]: Changes since last sync
+[o]: Overlapping classes
+[f]: Free time between classes
+[p]: Buildings and rooms
 [Backspace][h]: Go back
 [/]: Start search
 [t]: Toggle clock
 [h]: Toggle help
 [q]: Quit"#;
 
+                let help_height = help_text.lines().count() as u16 + 6;
                 let s = Rect {
                     x: (size.width / 2).saturating_sub(25),
-                    y: (size.height.saturating_sub(15)) / 2,
+                    y: (size.height.saturating_sub(help_height)) / 2,
                     width: 50,
-                    height: 15,
+                    height: help_height,
                 };
 
                 let overlay_area = center_rect(80, 80, s);
                 f.render_widget(Clear, overlay_area);
-                let bg_block = Block::default().style(Style::default().bg(Color::Black));
+                let bg_block = Block::default().style(theme().overlay);
                 f.render_widget(bg_block, overlay_area);
 
                 let overlay = Paragraph::new(help_text)
@@ -552,7 +565,7 @@ pub fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Res
                         Block::default()
                             .borders(Borders::ALL)
                             .title("Help")
-                            .style(Style::default().bg(Color::Black).fg(Color::White)),
+                            .style(theme().overlay),
                     )
                     .alignment(Alignment::Center);
                 f.render_widget(overlay, overlay_area);
@@ -574,6 +587,23 @@ pub fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Res
         
         if event::poll(std::time::Duration::from_millis(200))? {
             if let Event::Key(key) = event::read()? {
+                if let Some(editor) = app.note_editor.as_mut() {
+                    match key.code {
+                        KeyCode::Esc => app.note_editor = None,
+                        KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            app.notes.set(&editor.target, &editor.text);
+                            app.notes.save();
+                            app.note_editor = None;
+                        }
+                        KeyCode::Enter => editor.text.push('\n'),
+                        KeyCode::Backspace => {
+                            editor.text.pop();
+                        }
+                        KeyCode::Char(c) => editor.text.push(c),
+                        _ => {}
+                    }
+                    continue;
+                }
                 
                 if app.show_help {
                     match key.code {
@@ -603,19 +633,75 @@ pub fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Res
                 }
 
                 
+                if app.show_changes {
+                    match key.code {
+                        KeyCode::Char('a') if !app.read_only => {
+                            app.changes.clear();
+                            app.changed_sessions.clear();
+                            save_changes(&app.changes);
+                            app.show_changes = false;
+                        }
+                        KeyCode::Char('u') | KeyCode::Esc | KeyCode::Backspace | KeyCode::Char('h') => {
+                            app.show_changes = false;
+                        }
+                        KeyCode::Up | KeyCode::Char('k') => {
+                            app.changes_scroll = app.changes_scroll.saturating_sub(1);
+                        }
+                        KeyCode::Down | KeyCode::Char('j') => {
+                            app.changes_scroll += 1;
+                        }
+                        KeyCode::PageUp => {
+                            app.changes_scroll = app.changes_scroll.saturating_sub(10);
+                        }
+                        KeyCode::PageDown => {
+                            app.changes_scroll += 10;
+                        }
+                        KeyCode::Home => {
+                            app.changes_scroll = 0;
+                        }
+                        KeyCode::End => {
+                            app.changes_scroll = app.changes.len();
+                        }
+                        KeyCode::Char('q') => break,
+                        _ => {}
+                    }
+                    continue;
+                }
+
                 if app.show_details {
                     match key.code {
                         KeyCode::Enter | KeyCode::Backspace | KeyCode::Char('h') => {
                             app.show_details = false;
+                            app.details_course = None;
                         }
                         KeyCode::Up | KeyCode::Char('k') => {
-                            if app.details_scroll_index > 0 {
-                                app.details_scroll_index -= 1;
-                            }
+                            app.details_scroll_index = app.details_scroll_index.saturating_sub(1);
                         }
                         KeyCode::Down | KeyCode::Char('j') => {
                             app.details_scroll_index += 1;
                         }
+                        KeyCode::Char(c @ ('n' | 'N')) if !app.read_only => {
+                            let course = app
+                                .details_course
+                                .or_else(|| app.selected.and_then(|i| final_displayed.get(i).copied()));
+                            if let Some(course) = course {
+                                let (target, title) = if c == 'N' {
+                                    let key = course_key(course);
+                                    (Some(NoteTarget::Course(key.clone())), format!("Note for all of {}", key))
+                                } else {
+                                    let title = format!(
+                                        "Note for {} {} on {}",
+                                        course.abbr.as_deref().unwrap_or("N/A"),
+                                        course.class_type.as_deref().unwrap_or(""),
+                                        course.date.as_deref().unwrap_or("N/A")
+                                    );
+                                    (session_key(course).map(NoteTarget::Session), title)
+                                };
+                                if let Some(target) = target {
+                                    app.note_editor = Some(NoteEditor::new(&app.notes, target, title));
+                                }
+                            }
+                        }
                         KeyCode::Char('q') => break,
                         _ => {}
                     }
@@ -659,26 +745,19 @@ pub fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Res
                             app.ignore_overlay_active = false;
                         }
                         KeyCode::Up | KeyCode::Char('k') => {
-                            if app.ignore_overlay_index > 0 {
-                                app.ignore_overlay_index -= 1;
-                            }
+                            app.ignore_overlay_index = app.ignore_overlay_index.saturating_sub(1);
                         }
                         KeyCode::Down | KeyCode::Char('j') => {
-                            if !app.unique_courses.is_empty() {
-                                app.ignore_overlay_index = cmp::min(
-                                    app.ignore_overlay_index + 1,
-                                    app.unique_courses.len().saturating_sub(1),
-                                );
-                            }
+                            app.ignore_overlay_index = cmp::min(
+                                app.ignore_overlay_index + 1,
+                                app.unique_courses.len().saturating_sub(1),
+                            );
                         }
                         KeyCode::Home => {
                             app.ignore_overlay_index = 0;
                         }
                         KeyCode::End => {
-                            if !app.unique_courses.is_empty() {
-                                app.ignore_overlay_index =
-                                    app.unique_courses.len().saturating_sub(1);
-                            }
+                            app.ignore_overlay_index = app.unique_courses.len().saturating_sub(1);
                         }
                         KeyCode::Enter => {
                             if let Some(course) = app.unique_courses.get(app.ignore_overlay_index) {
@@ -697,10 +776,58 @@ pub fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Res
                 }
 
                 
+                if app.export_overlay_active {
+                    match key.code {
+                        KeyCode::Backspace | KeyCode::Char('e') | KeyCode::Char('h') => {
+                            app.export_overlay_active = false;
+                        }
+                        KeyCode::Up | KeyCode::Char('k') if app.export_overlay_index > 0 => {
+                            app.export_overlay_index -= 1;
+                        }
+                        KeyCode::Down | KeyCode::Char('j') => {
+                            app.export_overlay_index = cmp::min(app.export_overlay_index + 1, EXPORT_FORMATS.len() - 1);
+                        }
+                        KeyCode::Enter => {
+                            let (_, extension) = EXPORT_FORMATS[app.export_overlay_index];
+                            let data = match extension {
+                                "csv" => Ok(to_csv(final_displayed, &app.notes)),
+                                "json" => to_json(final_displayed, &app.notes, app.last_update),
+                                _ => Ok(to_ics(final_displayed, &app.notes, app.last_update)),
+                            };
+                            let path = export_path(extension);
+                            let res = data.map_err(io::Error::from).and_then(|data| fs::write(&path, data));
+                            app.export_message = Some(match res {
+                                Ok(()) => format!("Saved {} classes to {}", final_displayed.len(), path.display()),
+                                Err(e) => format!("Export failed: {}", e),
+                            });
+                        }
+                        KeyCode::Char('q') => break,
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                let handled = match app.view {
+                    View::Table => false,
+                    View::Week => handle_week_key(app, &displayed, key.code),
+                    View::Day => handle_day_key(app, &displayed, key.code),
+                    View::Month => handle_month_key(app, final_displayed, key.code),
+                    View::Courses => handle_courses_key(app, &displayed, key.code, now),
+                    View::Exams => handle_exams_key(app, key.code, now),
+                    View::Results => handle_results_key(app, key.code),
+                    View::Conflicts => handle_conflicts_key(app, key.code, now),
+                    View::Free => handle_free_key(app, &active, key.code, now),
+                    View::Places => handle_places_key(app, key.code),
+                };
+                if handled {
+                    continue;
+                }
+
+                
                 match key.code {
                     KeyCode::Char('q') => break,
                     // throw a special key to the event loop to force a refresh
-                    KeyCode::Char('s') => {
+                    KeyCode::Char('s') if !app.read_only => {
                         return Err(io::Error::new(io::ErrorKind::Interrupted, "forced refresh"));
                     }
                     KeyCode::Enter | KeyCode::Char('l') => {
@@ -719,6 +846,54 @@ pub fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Res
                     KeyCode::Char('i') => {
                         app.ignore_overlay_active = !app.ignore_overlay_active;
                     }
+                    KeyCode::Char('e') => {
+                        app.export_overlay_active = true;
+                        app.export_message = None;
+                    }
+                    KeyCode::Char('w') => {
+                        app.view = View::Week;
+                        app.view_date = now.date();
+                        app.view_selected = None;
+                    }
+                    KeyCode::Char('d') => {
+                        app.view = View::Day;
+                        app.view_date = now.date();
+                        app.view_selected = None;
+                    }
+                    KeyCode::Char('m') => {
+                        app.view = View::Month;
+                        app.view_date = now.date();
+                    }
+                    KeyCode::Char('c') => {
+                        app.view = View::Courses;
+                        app.view_selected = None;
+                        app.courses_group = None;
+                    }
+                    KeyCode::Char('x') => {
+                        app.view = View::Exams;
+                        app.view_selected = None;
+                    }
+                    KeyCode::Char('r') => {
+                        app.view = View::Results;
+                        app.view_selected = None;
+                    }
+                    KeyCode::Char('u') => {
+                        app.show_changes = true;
+                        app.changes_scroll = 0;
+                    }
+                    KeyCode::Char('o') => {
+                        app.view = View::Conflicts;
+                        app.view_selected = None;
+                    }
+                    KeyCode::Char('p') => {
+                        app.view = View::Places;
+                        app.view_selected = None;
+                    }
+                    KeyCode::Char('f') => {
+                        app.view = View::Free;
+                        app.view_date = now.date();
+                        app.view_selected = None;
+                    }
                     KeyCode::Char('/') => {
                         app.search_mode = true;
                         if app.search_query.is_none() {
@@ -837,18 +1012,14 @@ pub fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Res
 
 fn build_status_msg(
     displayed: &[&CourseAction],
+    travel_warnings: &[TravelWarning],
     now: NaiveDateTime,
 ) -> (Text<'static>, Option<(f64, String)>) {
     let mut text = Text::default();
     let mut gauge_data = None;
 
-    
-    if let Some(course) = displayed.iter().find(|&&c| is_course_ongoing(c, now)) {
-        if let Some((start, end)) = parse_course_datetime(*course) {
-            let total = (end - start).num_seconds().max(1) as f64;
-            let elapsed = (now - start).num_seconds().max(0) as f64;
-            let progress = (elapsed / total).min(1.0);
-
+    match ClassStatus::compute(displayed, now) {
+        ClassStatus::Ongoing { course, end, progress, .. } => {
             let diff = end - now;
             let label = format!(
                 "Ongoing: {}, {}h {}m {}s left",
@@ -863,19 +1034,8 @@ fn build_status_msg(
                 Style::default().add_modifier(Modifier::BOLD),
             ))));
             gauge_data = Some((progress, format!("{:.1}%", progress * 100.0)));
-        } else {
-            text.extend(Text::from(format!("Ongoing: {}", course.name.as_deref().unwrap_or("N/A"))));
-        }
-    }
-    
-    else if let Some(course) = displayed.iter().find(|&&c| {
-        if let Some((start, _)) = parse_course_datetime(c) {
-            start > now
-        } else {
-            false
         }
-    }) {
-        if let Some((start, _)) = parse_course_datetime(*course) {
+        ClassStatus::Next { course, start, progress, .. } => {
             let diff = start - now;
             let status_text = format!(
                 "Next class: {} in {}h {}m {}s",
@@ -885,43 +1045,23 @@ fn build_status_msg(
                 diff.num_seconds() % 60
             );
 
-            
-            let prev_end_time = displayed
-                .iter()
-                .filter_map(|&c| {
-                    if let Some((_, end)) = parse_course_datetime(c) {
-                        if end <= now {
-                            Some(end)
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                })
-                .max();
-
-            
-            let progress = if let Some(prev_end) = prev_end_time {
-                let total_free_time = (start - prev_end).num_seconds().max(1) as f64;
-                let elapsed_free_time = (now - prev_end).num_seconds().max(0) as f64;
-                (elapsed_free_time / total_free_time).min(1.0)
-            } else {
-                0.0
-            };
-
             text.extend(Text::from(Spans::from(Span::styled(
                 status_text,
                 Style::default().add_modifier(Modifier::BOLD),
             ))));
             gauge_data = Some((progress, format!("Free time: {:.1}%", progress * 100.0)));
-        } else {
-            text.extend(Text::from("Status unavailable"));
+        }
+        ClassStatus::None => {
+            text.extend(Text::from("No upcoming classes."));
         }
     }
-    
-    else {
-        text.extend(Text::from("No upcoming classes."));
+
+    // Only one line fits, the way to the next class matters more than the rest.
+    if let Some(warning) = leave_now(travel_warnings, now) {
+        text = Text::from(Spans::from(Span::styled(
+            format!("Leave now to make it to {} ({}m walk)", warning.destination(), warning.travel_minutes),
+            theme().travel,
+        )));
     }
 
     (text, gauge_data)
@@ -929,6 +1069,35 @@ fn build_status_msg(
 
 
 
+/// The indicator column: the cursor, ONGOING or NEXT, then CONFLICT and ⚠ TRAVEL
+/// next to them, problems with the next class matter most. The rest only fills an empty cell.
+fn row_indicator(course: &CourseAction, idx: usize, next_index: Option<usize>, app: &App, now: NaiveDateTime) -> String {
+    let selected = app.selected == Some(idx);
+    let mut labels = Vec::new();
+    if selected {
+        labels.push(">");
+    }
+    if is_course_ongoing(course, now) {
+        labels.push("ONGOING");
+    } else if Some(idx) == next_index {
+        labels.push("NEXT");
+    }
+    if session_key(course).is_some_and(|key| app.conflicting_sessions.contains(&key)) {
+        labels.push("CONFLICT");
+    }
+    if warning_for(&app.travel_warnings, course).is_some() {
+        labels.push("⚠ TRAVEL");
+    }
+    if labels.len() == usize::from(selected) {
+        if app.selected.is_none() && is_exam(course) {
+            labels.push("EXAM");
+        } else if app.selected.is_none() && session_key(course).is_some_and(|key| app.changed_sessions.contains(&key)) {
+            labels.push("CHANGED");
+        }
+    }
+    labels.join(" ")
+}
+
 fn build_table_row<'a>(
     course: &'a CourseAction,
     visible_index: usize,
@@ -945,9 +1114,17 @@ fn build_table_row<'a>(
         course.timeTo.as_deref().unwrap_or("N/A")
     );
     let typ = &course.class_type;
-    let course_display = &course.name;
+    let course_display = course.name.as_deref().unwrap_or("N/A");
+    // Marks classes with own notes.
+    let course_display = if app.notes.text(course).is_some() {
+        format!("✎ {}", course_display)
+    } else {
+        course_display.to_string()
+    };
     let place = course.place.as_deref().unwrap_or("N/A");
     let room = course.room.as_deref().unwrap_or("N/A");
+    let changed = session_key(course).is_some_and(|key| app.changed_sessions.contains(&key));
+    let conflicting = session_key(course).is_some_and(|key| app.conflicting_sessions.contains(&key));
 
     let day_display = if visible_index == 0 {
         course.day.as_deref().unwrap_or("")
@@ -964,51 +1141,20 @@ fn build_table_row<'a>(
         }
     };
 
-    let indicator = if let Some(selected) = app.selected {
-        if selected == idx {
-            if is_course_ongoing(course, now) {
-                "> ONGOING"
-            } else if Some(idx) == next_index {
-                "> NEXT"
-            } else {
-                ">"
-            }
-        } else if is_course_ongoing(course, now) {
-            "ONGOING"
-        } else if Some(idx) == next_index {
-            "NEXT"
-        } else {
-            ""
-        }
-    } else if is_course_ongoing(course, now) {
-        "ONGOING"
-    } else if Some(idx) == next_index {
-        "NEXT"
-    } else {
-        ""
-    };
+    let indicator = row_indicator(course, idx, next_index, app, now);
 
     let style = if let Some(selected) = app.selected {
         if selected == idx {
-            Style::default()
-                .fg(Color::Black)
-                .bg(Color::Blue)
-                .add_modifier(Modifier::BOLD)
+            theme().selected
         } else {
             Style::default()
         }
     } else if let Some(auto_idx) = app.upcoming_index() {
         if idx == auto_idx {
             if is_course_ongoing(course, now) {
-                Style::default()
-                    .fg(Color::Black)
-                    .bg(Color::Red)
-                    .add_modifier(Modifier::BOLD)
+                theme().ongoing
             } else {
-                Style::default()
-                    .fg(Color::Black)
-                    .bg(Color::Green)
-                    .add_modifier(Modifier::BOLD)
+                theme().next
             }
         } else {
             Style::default()
@@ -1016,14 +1162,28 @@ fn build_table_row<'a>(
     } else {
         Style::default()
     };
+    // Conflicts, changes, exams and own events stand out from the classes unless highlighted.
+    let style = if style != Style::default() {
+        style
+    } else if conflicting {
+        theme().conflict
+    } else if changed {
+        theme().changed
+    } else if is_exam(course) {
+        theme().exam
+    } else if is_user_event(course) {
+        theme().event
+    } else {
+        style
+    };
 
     Row::new(vec![
-        indicator.to_string(),
+        indicator,
         day_display.to_string(),
         date.to_string(),
         time,
         typ.as_deref().unwrap_or("N/A").to_string(),
-        course_display.as_deref().unwrap_or("N/A").to_string(),
+        course_display,
         place.to_string(),
         room.to_string(),
     ])
@@ -1031,10 +1191,58 @@ fn build_table_row<'a>(
 }
 
 
+fn draw_changes_overlay<B: Backend>(f: &mut ratatui::Frame<B>, area: Rect, app: &mut App) {
+    let overlay_area = center_rect(80, 60, area);
+    f.render_widget(Clear, overlay_area);
+
+    // One change per line, scrolled so the last one ends at the bottom at most.
+    let visible = overlay_area.height.saturating_sub(2) as usize;
+    app.changes_scroll = app.changes_scroll.min(app.changes.len().saturating_sub(visible));
+
+    let lines: Vec<Spans> = if app.changes.is_empty() {
+        vec![Spans::from("Nothing changed.")]
+    } else {
+        app.changes
+            .iter()
+            .map(|change| {
+                let style = match change.kind {
+                    ChangeKind::Removed => theme().bad,
+                    ChangeKind::Added => theme().good,
+                    _ => theme().info,
+                };
+                Spans::from(vec![
+                    Span::styled(format!("{:<13}", change.kind.label()), style.add_modifier(Modifier::BOLD)),
+                    Span::raw(format!("{} {} ", change.abbr, change.class_type)),
+                    Span::styled(change.summary(), style),
+                ])
+            })
+            .collect()
+    };
+    let position = if app.changes.len() > visible {
+        format!("{}-{} of {}, [j/k] scroll, ", app.changes_scroll + 1, app.changes_scroll + visible, app.changes.len())
+    } else {
+        String::new()
+    };
+    let title = if app.read_only {
+        format!("Changes since last sync ({}[u] close)", position)
+    } else {
+        format!("Changes since last sync ({}[a]cknowledge, [u] close)", position)
+    };
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .style(theme().overlay),
+        )
+        .scroll((app.changes_scroll as u16, 0));
+    f.render_widget(paragraph, overlay_area);
+}
+
 fn draw_ignore_overlay<B: Backend>(f: &mut ratatui::Frame<B>, area: Rect, app: &mut App) {
     let overlay_area = center_rect(60, 60, area);
     f.render_widget(Clear, overlay_area);
-    let bg_block = Block::default().style(Style::default().bg(Color::Black));
+    let bg_block = Block::default().style(theme().overlay);
     f.render_widget(bg_block, overlay_area);
 
     let ignore_chunks = Layout::default()
@@ -1095,7 +1303,7 @@ fn draw_ignore_overlay<B: Backend>(f: &mut ratatui::Frame<B>, area: Rect, app: &
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .style(Style::default().bg(Color::Black).fg(Color::White)),
+                .style(theme().overlay),
         )
         .alignment(Alignment::Left);
     f.render_widget(list_paragraph, ignore_chunks[1]);
@@ -1115,6 +1323,71 @@ fn draw_ignore_overlay<B: Backend>(f: &mut ratatui::Frame<B>, area: Rect, app: &
 }
 
 
+const EXPORT_FORMATS: [(&str, &str); 3] = [
+    ("CSV spreadsheet", "csv"),
+    ("Normalized JSON", "json"),
+    ("iCalendar", "ics"),
+];
+
+fn draw_export_overlay<B: Backend>(f: &mut ratatui::Frame<B>, area: Rect, app: &App, shown: usize) {
+    let overlay_area = center_rect(60, 40, area);
+    f.render_widget(Clear, overlay_area);
+    let bg_block = Block::default().style(theme().overlay);
+    f.render_widget(bg_block, overlay_area);
+
+    let export_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)].as_ref())
+        .split(overlay_area);
+
+    let header = app
+        .export_message
+        .clone()
+        .unwrap_or_else(|| format!("Export {} shown classes (Enter to save)", shown));
+    let header_paragraph = Paragraph::new(header)
+        .block(Block::default().borders(Borders::ALL).title("Export"))
+        .alignment(Alignment::Left);
+    f.render_widget(header_paragraph, export_chunks[0]);
+
+    let text_lines: Vec<String> = EXPORT_FORMATS
+        .iter()
+        .enumerate()
+        .map(|(i, (label, extension))| {
+            let prefix = if i == app.export_overlay_index { ">" } else { " " };
+            format!("{} {} -> {}", prefix, label, export_path(extension).display())
+        })
+        .collect();
+
+    let list_paragraph = Paragraph::new(text_lines.join("\n"))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .style(theme().overlay),
+        )
+        .alignment(Alignment::Left);
+    f.render_widget(list_paragraph, export_chunks[1]);
+}
+
+
+fn draw_note_editor<B: Backend>(f: &mut ratatui::Frame<B>, area: Rect, editor: &NoteEditor) {
+    let overlay_area = center_rect(60, 50, area);
+    f.render_widget(Clear, overlay_area);
+
+    // Keep the end of a long note, where the cursor is, in view.
+    let visible = overlay_area.height.saturating_sub(2) as usize;
+    let scroll = editor.text.lines().count().saturating_sub(visible) as u16;
+    let editor_paragraph = Paragraph::new(format!("{}█", editor.text))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("{} ([Ctrl+S] save, [Esc] cancel)", editor.title))
+                .style(theme().overlay),
+        )
+        .wrap(Wrap { trim: false })
+        .scroll((scroll, 0));
+    f.render_widget(editor_paragraph, overlay_area);
+}
+
 fn center_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -1145,8 +1418,7 @@ fn draw_course_details<B: Backend>(
     f: &mut ratatui::Frame<B>,
     size: Rect,
     course: &CourseAction,
-    selected: usize,
-    next_index: Option<usize>,
+    is_next: bool,
     now: NaiveDateTime,
     app: &mut App,
 ) {
@@ -1230,6 +1502,23 @@ fn draw_course_details<B: Backend>(
         )
     };
 
+    // Own notes go first, they are what the user wants to see again.
+    let mut own_notes = String::new();
+    if let Some(note) = app.notes.course_note(course) {
+        own_notes.push_str(&format!("My course note: {}\n", note.replace('\n', "\n  ")));
+    }
+    if let Some(note) = app.notes.session_note(course) {
+        own_notes.push_str(&format!("My note: {}\n", note.replace('\n', "\n  ")));
+    }
+    // Where the class is, for anyone who does not know the codes yet.
+    let location = app
+        .places
+        .describe(course.place.as_deref().unwrap_or(""), course.room.as_deref().unwrap_or(""))
+        .into_iter()
+        .map(|line| line + "\n")
+        .collect::<String>();
+    let details_text = own_notes + &location + &details_text;
+
     f.render_widget(Clear, size);
 
     // Split details_text into lines
@@ -1257,7 +1546,8 @@ fn draw_course_details<B: Backend>(
     }
 
     let final_text = displayed_lines.join("\n");
-    let details_block = Block::default().borders(Borders::ALL).title("Details");
+    let details_title = if app.read_only { "Details" } else { "Details ([n]ote, [N] course note)" };
+    let details_block = Block::default().borders(Borders::ALL).title(details_title);
     let details_paragraph = Paragraph::new(final_text)
         .block(details_block)
         .alignment(Alignment::Left);
@@ -1265,7 +1555,7 @@ fn draw_course_details<B: Backend>(
 
     let label_text = if is_course_ongoing(course, now) {
         "ONGOING"
-    } else if Some(selected) == next_index {
+    } else if is_next {
         "NEXT"
     } else {
         ""
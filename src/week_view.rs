@@ -0,0 +1,318 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use crossterm::event::KeyCode;
+use ratatui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+use crate::app::{App, View};
+use crate::events::is_user_event;
+use crate::exams::is_exam;
+use crate::free::{course_times, free_slots};
+use crate::theme::theme;
+use crate::timetable::{is_course_ongoing, parse_course_datetime, weekday_abbr, CourseAction};
+
+pub fn week_monday(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Courses of the week around `date`, sorted by start. Weekends only when shown.
+pub fn week_courses<'a>(displayed: &[&'a CourseAction], date: NaiveDate, weekends: bool) -> Vec<&'a CourseAction> {
+    let monday = week_monday(date);
+    let days = if weekends { 7 } else { 5 };
+    let mut courses: Vec<&CourseAction> = displayed
+        .iter()
+        .filter(|c| {
+            parse_course_datetime(c).is_some_and(|(start, _)| {
+                start.date() >= monday && start.date() < monday + Duration::days(days)
+            })
+        })
+        .copied()
+        .collect();
+    courses.sort_by_key(|c| parse_course_datetime(c).map(|(start, _)| start));
+    courses
+}
+
+/// Puts overlapping classes side by side: returns each course's lane and the lane count.
+pub fn assign_lanes(times: &[(NaiveDateTime, NaiveDateTime)]) -> (Vec<usize>, usize) {
+    let mut lane_ends: Vec<NaiveDateTime> = Vec::new();
+    let mut lanes = Vec::with_capacity(times.len());
+    for &(start, end) in times {
+        match lane_ends.iter().position(|&lane_end| lane_end <= start) {
+            Some(lane) => {
+                lane_ends[lane] = end;
+                lanes.push(lane);
+            }
+            None => {
+                lane_ends.push(end);
+                lanes.push(lane_ends.len() - 1);
+            }
+        }
+    }
+    (lanes, lane_ends.len().max(1))
+}
+
+/// First and last hour to draw, covering all given classes and at least 8-16.
+pub fn hour_range(courses: &[&CourseAction]) -> (u32, u32) {
+    let mut first = 8;
+    let mut last = 16;
+    for course in courses {
+        if let Some((start, end)) = parse_course_datetime(course) {
+            first = first.min(start.hour());
+            last = last.max(if end.minute() > 0 { end.hour() + 1 } else { end.hour() });
+        }
+    }
+    (first, last.min(24))
+}
+
+/// Maps times of a day onto terminal rows.
+pub struct TimeScale {
+    pub top: u16,
+    pub rows: u16,
+    pub first_hour: u32,
+    pub last_hour: u32,
+    pub minutes_per_row: f64,
+}
+
+impl TimeScale {
+    pub fn new(top: u16, rows: u16, (first_hour, last_hour): (u32, u32)) -> Self {
+        let minutes = ((last_hour - first_hour) * 60) as f64;
+        TimeScale {
+            top,
+            rows,
+            first_hour,
+            last_hour,
+            minutes_per_row: minutes / rows.max(1) as f64,
+        }
+    }
+
+    pub fn row(&self, time: NaiveTime) -> u16 {
+        let minutes = (time.hour() as i64 - self.first_hour as i64) * 60 + time.minute() as i64;
+        let row = (minutes.max(0) as f64 / self.minutes_per_row) as u16;
+        self.top + row.min(self.rows)
+    }
+}
+
+pub fn block_style(selected: bool, ongoing: bool, course: &CourseAction) -> Style {
+    let theme = theme();
+    if selected {
+        theme.selected
+    } else if ongoing {
+        theme.ongoing
+    } else if is_exam(course) {
+        theme.exam_block
+    } else if is_user_event(course) {
+        theme.event_block
+    } else {
+        theme.block
+    }
+}
+
+pub fn draw_week_view<B: Backend>(
+    f: &mut ratatui::Frame<B>,
+    area: Rect,
+    app: &App,
+    displayed: &[&CourseAction],
+    now: NaiveDateTime,
+) {
+    let monday = week_monday(app.view_date);
+    let days = if app.show_weekends { 7 } else { 5 };
+    let courses = week_courses(displayed, app.view_date, app.show_weekends);
+
+    let mut title = format!(
+        "Week {} - {} ([</>] week, [a]ll days)",
+        monday.format("%-d.%-m."),
+        (monday + Duration::days(days - 1)).format("%-d.%-m.%Y")
+    );
+    if !app.group_names.is_empty() {
+        title.push_str(&format!(" - free with {}", app.group_names.join(", ")));
+    }
+    f.render_widget(Clear, area);
+    f.render_widget(Block::default().borders(Borders::ALL).title(title), area);
+
+    let inner = Rect {
+        x: area.x + 1,
+        y: area.y + 1,
+        width: area.width.saturating_sub(2),
+        height: area.height.saturating_sub(2),
+    };
+    if inner.height < 3 || inner.width < 20 {
+        return;
+    }
+
+    let axis_width = 6;
+    let column_width = (inner.width - axis_width) / days as u16;
+    let (mut first_hour, mut last_hour) = hour_range(&courses);
+    // With others' busy times the whole searched day is of interest.
+    let group_busy = if app.group_names.is_empty() {
+        Vec::new()
+    } else {
+        first_hour = first_hour.min(app.free_time.day_start.hour());
+        last_hour = last_hour.max(app.free_time.day_end.hour() + (app.free_time.day_end.minute() > 0) as u32).min(24);
+        // Ignored classes and search results do not change when someone is busy.
+        let active: Vec<&CourseAction> = app
+            .courses
+            .iter()
+            .filter(|course| course.id.is_none_or(|id| !app.ignored_ids.contains(&id)))
+            .copied()
+            .collect();
+        let mut busy = course_times(&active);
+        busy.extend(&app.group_busy);
+        busy
+    };
+    let scale = TimeScale::new(inner.y + 1, inner.height - 1, (first_hour, last_hour));
+
+    for hour in scale.first_hour..scale.last_hour {
+        let y = scale.row(NaiveTime::from_hms_opt(hour, 0, 0).unwrap());
+        let label = Paragraph::new(format!("{:02}:00", hour)).style(theme().muted);
+        f.render_widget(label, Rect { x: inner.x, y, width: axis_width, height: 1 });
+    }
+
+    for day in 0..days {
+        let date = monday + Duration::days(day);
+        let x = inner.x + axis_width + day as u16 * column_width;
+        let header_style = if date == now.date() {
+            theme().today
+        } else {
+            Style::default().add_modifier(Modifier::BOLD)
+        };
+        let header = Paragraph::new(format!("{} {}", weekday_abbr(date), date.format("%-d.%-m.")))
+            .alignment(Alignment::Center)
+            .style(header_style);
+        f.render_widget(header, Rect { x, y: inner.y, width: column_width, height: 1 });
+
+        let day_courses: Vec<(usize, &CourseAction, NaiveDateTime, NaiveDateTime)> = courses
+            .iter()
+            .enumerate()
+            .filter_map(|(i, c)| parse_course_datetime(c).map(|(start, end)| (i, *c, start, end)))
+            .filter(|(_, _, start, _)| start.date() == date)
+            .collect();
+        let times: Vec<_> = day_courses.iter().map(|&(_, _, start, end)| (start, end)).collect();
+        let (lanes, lane_count) = assign_lanes(&times);
+        let lane_width = (column_width.saturating_sub(1) / lane_count as u16).max(1);
+
+        // Others' busy times as shading, the free time everybody shares in green.
+        for &(start, end) in app.group_busy.iter().filter(|(start, end)| start.date() <= date && end.date() >= date) {
+            let top = scale.row(start.max(date.and_hms_opt(0, 0, 0).unwrap()).time());
+            let bottom = if end.date() > date { scale.top + scale.rows } else { scale.row(end.time()) }.max(top + 1);
+            let rect = Rect {
+                x,
+                y: top,
+                width: column_width.saturating_sub(1),
+                height: (bottom - top).min(inner.y + inner.height - top),
+            };
+            let shade = Paragraph::new("░".repeat(rect.width as usize * rect.height as usize))
+                .wrap(Wrap { trim: false })
+                .style(theme().muted);
+            f.render_widget(shade, rect);
+        }
+        if app.group_range.is_some_and(|(first, last)| first <= date && date <= last) {
+            for slot in free_slots(&group_busy, date, date, &app.free_time, now) {
+                let top = scale.row(slot.start.time());
+                let bottom = scale.row(slot.end.time()).max(top + 1);
+                let rect = Rect {
+                x,
+                y: top,
+                width: column_width.saturating_sub(1),
+                height: (bottom - top).min(inner.y + inner.height - top),
+            };
+                let text = format!("free\n{}-{}", slot.start.format("%H:%M"), slot.end.format("%H:%M"));
+                f.render_widget(Clear, rect);
+                f.render_widget(Paragraph::new(text).style(theme().free), rect);
+            }
+        }
+
+        // Blocks are drawn over the line, the ongoing one is highlighted instead.
+        if date == now.date() && now.hour() >= scale.first_hour {
+            let y = scale.row(now.time());
+            if y < inner.y + inner.height {
+                let line = Paragraph::new("─".repeat(column_width as usize))
+                    .style(theme().now);
+                f.render_widget(line, Rect { x, y, width: column_width, height: 1 });
+            }
+        }
+
+        for (&(i, course, start, end), lane) in day_courses.iter().zip(lanes) {
+            let top = scale.row(start.time());
+            let bottom = scale.row(end.time()).max(top + 1);
+            let rect = Rect {
+                x: x + lane as u16 * lane_width,
+                y: top,
+                width: lane_width,
+                height: (bottom - top).min(inner.y + inner.height - top),
+            };
+            let text = format!(
+                "{} {}\n{}\n{} {}",
+                course.abbr.as_deref().unwrap_or("N/A"),
+                course.class_type.as_deref().unwrap_or(""),
+                course.timeFrom.as_deref().unwrap_or(""),
+                course.place.as_deref().unwrap_or(""),
+                course.room.as_deref().unwrap_or(""),
+            );
+            let style = block_style(app.view_selected == Some(i), is_course_ongoing(course, now), course);
+            f.render_widget(Clear, rect);
+            f.render_widget(Paragraph::new(text).style(style), rect);
+        }
+    }
+}
+
+/// Moving through and opening the classes of a calendar view.
+pub fn handle_selection_key<'a>(app: &mut App<'a>, courses: &[&'a CourseAction], code: KeyCode) -> bool {
+    match code {
+        KeyCode::Down | KeyCode::Char('j') if !courses.is_empty() => {
+            app.view_selected = Some(app.view_selected.map_or(0, |i| (i + 1).min(courses.len() - 1)));
+        }
+        KeyCode::Up | KeyCode::Char('k') if !courses.is_empty() => {
+            app.view_selected = Some(app.view_selected.map_or(0, |i| i.saturating_sub(1)));
+        }
+        KeyCode::Enter | KeyCode::Char('l') => {
+            if let Some(&course) = app.view_selected.and_then(|i| courses.get(i)) {
+                app.details_course = Some(course);
+                app.show_details = true;
+            }
+        }
+        _ => return false,
+    }
+    true
+}
+
+/// Keys of the week view. Returns false for keys left to the main key handling.
+pub fn handle_week_key<'a>(app: &mut App<'a>, displayed: &[&'a CourseAction], code: KeyCode) -> bool {
+    let courses = week_courses(displayed, app.view_date, app.show_weekends);
+    if handle_selection_key(app, &courses, code) {
+        return true;
+    }
+    match code {
+        KeyCode::Left | KeyCode::Char('<') | KeyCode::PageUp => {
+            app.view_date -= Duration::days(7);
+            app.view_selected = None;
+        }
+        KeyCode::Right | KeyCode::Char('>') | KeyCode::PageDown => {
+            app.view_date += Duration::days(7);
+            app.view_selected = None;
+        }
+        KeyCode::Char('a') => {
+            app.show_weekends = !app.show_weekends;
+            app.view_selected = None;
+        }
+        KeyCode::Char('d') => {
+            // Straight into the day of the selected block.
+            if let Some((start, _)) = app
+                .view_selected
+                .and_then(|i| courses.get(i))
+                .and_then(|course| parse_course_datetime(course))
+            {
+                app.view_date = start.date();
+            }
+            app.view = View::Day;
+            app.view_selected = None;
+        }
+        KeyCode::Char('w') | KeyCode::Backspace | KeyCode::Char('h') => {
+            app.view = View::Table;
+        }
+        _ => return false,
+    }
+    true
+}